@@ -1,3 +1,11 @@
 fn main() {
-    prost_build::compile_protos(&["proto/meteora.proto"], &["proto"]).unwrap();
+    prost_build::compile_protos(
+        &[
+            "proto/sf/substreams/sink/database/v1/database.proto",
+            "proto/sf/substreams/entity/v1/entity.proto",
+            "proto/sf/substreams/sink/kv/v1/kv.proto",
+        ],
+        &["proto"],
+    )
+    .unwrap();
 }
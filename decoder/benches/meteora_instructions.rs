@@ -0,0 +1,101 @@
+// Per-block processing time of `meteora_instructions`, the single pass every block-driven module reads.
+// Runs over the `*.binpb` blocks in $METEORA_BENCH_BLOCKS (e.g. recorded large mainnet blocks), or over a
+// synthetic 1500 transaction block built from the DLMM swap fixture when it is unset:
+//
+//   METEORA_BENCH_BLOCKS=/path/to/blocks cargo bench -p meteora-decoder --target x86_64-unknown-linux-gnu
+//
+// The base58 encoding of every program ID, which matching used to require, is timed alongside for reference.
+
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use meteora_decoder::meteora_instructions;
+use prost::Message;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+const ITERATIONS: u32 = 50;
+const SYNTHETIC_TRANSACTIONS: usize = 1500;
+// One transaction in this many invokes Meteora, the rest invoke another program
+const METEORA_EVERY: usize = 25;
+
+fn blocks() -> Vec<(String, Block)> {
+    let Ok(dir) = std::env::var("METEORA_BENCH_BLOCKS") else {
+        return vec![("synthetic".to_string(), synthetic_block())];
+    };
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "binpb"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (name, Block::decode(fs::read(&path).unwrap().as_slice()).unwrap())
+        })
+        .collect()
+}
+
+// The fixture swap repeated, with the DLMM program key swapped for another program in most copies
+fn synthetic_block() -> Block {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/synthetic_dlmm_swap.binpb");
+    let mut block = Block::decode(fs::read(fixture).unwrap().as_slice()).unwrap();
+    let swap = block.transactions.remove(0);
+    let other_program = vec![42u8; 32];
+    let other_id = bs58::encode(&other_program).into_string();
+    let dlmm_id = meteora_decoder::DLMM_PROGRAM;
+
+    block.transactions = (0..SYNTHETIC_TRANSACTIONS)
+        .map(|index| {
+            let mut trx = swap.clone();
+            if index % METEORA_EVERY != 0 {
+                let message = trx.transaction.as_mut().unwrap().message.as_mut().unwrap();
+                for key in message.account_keys.iter_mut().filter(|key| key.as_slice() == meteora_decoder::DLMM_PROGRAM_ID) {
+                    *key = other_program.clone();
+                }
+                let meta = trx.meta.as_mut().unwrap();
+                for log in meta.log_messages.iter_mut() {
+                    *log = log.replace(dlmm_id, &other_id);
+                }
+            }
+            trx.transaction.as_mut().unwrap().signatures = vec![(index as u64).to_le_bytes().repeat(8)];
+            trx
+        })
+        .collect();
+    block
+}
+
+fn encode_program_ids(block: &Block) -> usize {
+    block
+        .transactions()
+        .flat_map(|trx| trx.walk_instructions())
+        .map(|inst| inst.program_id().to_string().len())
+        .sum()
+}
+
+fn time<T>(block: &Block, run: fn(&Block) -> T) -> Duration {
+    black_box(run(block));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(run(black_box(block)));
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    for (name, block) in blocks() {
+        let instructions = block.transactions().map(|trx| trx.walk_instructions().count()).sum::<usize>();
+        let kept = meteora_instructions(&block).transactions.len();
+
+        println!(
+            "{name}: {} transactions, {instructions} instructions, {kept} Meteora transactions",
+            block.transactions.len()
+        );
+        println!("  meteora_instructions  {:>10.1?}/block", time(&block, meteora_instructions));
+        println!("  encode program IDs    {:>10.1?}/block", time(&block, encode_program_ids));
+    }
+}
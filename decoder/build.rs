@@ -0,0 +1,3 @@
+fn main() {
+    prost_build::compile_protos(&["../proto/meteora.proto", "../proto/spl_account.proto"], &["../proto"]).unwrap();
+}
@@ -0,0 +1,281 @@
+use borsh::BorshDeserialize;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const ADD_BALANCE_LIQUIDITY: [u8; 8] = [168, 227, 50, 62, 189, 171, 84, 176];
+const REMOVE_BALANCE_LIQUIDITY: [u8; 8] = [133, 109, 44, 179, 56, 238, 114, 33];
+const ADD_IMBALANCE_LIQUIDITY: [u8; 8] = [79, 35, 122, 84, 173, 15, 93, 191];
+const REMOVE_LIQUIDITY_SINGLE_SIDE: [u8; 8] = [84, 84, 177, 66, 254, 185, 10, 251];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    SWAP,
+    ADD_BALANCE_LIQUIDITY,
+    REMOVE_BALANCE_LIQUIDITY,
+    ADD_IMBALANCE_LIQUIDITY,
+    REMOVE_LIQUIDITY_SINGLE_SIDE,
+];
+
+// IDL instructions the decoder leaves undecoded, so they are not reported as unknown discriminators
+pub const UNDECODED_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("initialize_permissioned_pool", [77, 85, 178, 157, 50, 48, 212, 126]),
+    ("initialize_permissionless_pool", [118, 173, 41, 157, 173, 72, 97, 103]),
+    ("initialize_permissionless_pool_with_fee_tier", [6, 135, 68, 147, 229, 82, 169, 113]),
+    ("initialize_permissionless_constant_product_pool_with_config", [7, 166, 138, 171, 206, 171, 236, 244]),
+    ("initialize_permissionless_constant_product_pool_with_config2", [48, 149, 220, 130, 61, 11, 9, 178]),
+    ("initialize_customizable_permissionless_constant_product_pool", [145, 24, 172, 194, 219, 125, 3, 190]),
+    ("enable_or_disable_pool", [128, 6, 228, 131, 55, 161, 52, 169]),
+    ("get_pool_info", [9, 48, 220, 101, 22, 240, 78, 200]),
+    ("bootstrap_liquidity", [4, 228, 215, 71, 225, 253, 119, 206]),
+    ("create_mint_metadata", [13, 70, 168, 41, 250, 100, 148, 90]),
+    ("create_lock_escrow", [54, 87, 165, 19, 69, 227, 218, 224]),
+    ("lock", [21, 19, 208, 43, 237, 62, 255, 87]),
+    ("claim_fee", [169, 32, 79, 137, 136, 232, 70, 137]),
+    ("partner_claim_fee", [57, 53, 176, 30, 123, 70, 52, 64]),
+    ("create_config", [201, 207, 243, 114, 75, 111, 47, 189]),
+    ("close_config", [145, 9, 72, 157, 95, 125, 61, 85]),
+    ("update_activation_point", [150, 62, 125, 219, 171, 220, 26, 237]),
+    ("withdraw_protocol_fees", [11, 68, 165, 98, 18, 208, 134, 73]),
+    ("set_whitelisted_vault", [12, 148, 94, 42, 55, 57, 83, 247]),
+    ("set_pool_fees", [102, 44, 158, 54, 205, 37, 126, 78]),
+    ("override_curve_param", [98, 86, 204, 51, 94, 71, 69, 187]),
+    ("transfer_admin", [42, 242, 66, 106, 228, 10, 111, 156]),
+];
+
+// Anchor event discriminators: sha256("event:<Name>")[..8]
+const SWAP_EVENT: [u8; 8] = [81, 108, 227, 190, 205, 208, 10, 196];
+const POOL_CREATED_EVENT: [u8; 8] = [202, 44, 41, 88, 104, 220, 157, 82];
+
+// Event discriminators known to the decoder
+pub const EVENTS: &[[u8; 8]] = &[
+    SWAP_EVENT,
+    POOL_CREATED_EVENT,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const SWAP_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (1, "user_source_token"),
+    (2, "user_destination_token"),
+    (5, "a_token_vault"),
+    (6, "b_token_vault"),
+    (9, "a_vault_lp"),
+    (10, "b_vault_lp"),
+    (11, "protocol_token_fee"),
+];
+const LIQUIDITY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (2, "user_pool_lp"),
+    (3, "a_vault_lp"),
+    (4, "b_vault_lp"),
+    (9, "a_token_vault"),
+    (10, "b_token_vault"),
+    (11, "user_a_token"),
+    (12, "user_b_token"),
+];
+const SINGLE_SIDE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (2, "user_pool_lp"),
+    (3, "a_vault_lp"),
+    (4, "b_vault_lp"),
+    (9, "a_token_vault"),
+    (10, "b_token_vault"),
+    (11, "user_destination_token"),
+];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapArgs {
+    pub in_amount: u64,
+    pub minimum_out_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct BalanceLiquidityArgs {
+    pub pool_token_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ImbalanceLiquidityArgs {
+    pub minimum_pool_token_amount: u64,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SingleSideArgs {
+    pub pool_token_amount: u64,
+    pub minimum_out_amount: u64,
+}
+
+#[derive(Debug)]
+pub enum PoolInstruction {
+    Swap(SwapArgs),
+    AddBalanceLiquidity(BalanceLiquidityArgs),
+    RemoveBalanceLiquidity(BalanceLiquidityArgs),
+    AddImbalanceLiquidity(ImbalanceLiquidityArgs),
+    RemoveLiquiditySingleSide(SingleSideArgs),
+}
+
+impl PoolInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoolInstruction::Swap(_) => "swap",
+            PoolInstruction::AddBalanceLiquidity(_) => "add_balance_liquidity",
+            PoolInstruction::RemoveBalanceLiquidity(_) => "remove_balance_liquidity",
+            PoolInstruction::AddImbalanceLiquidity(_) => "add_imbalance_liquidity",
+            PoolInstruction::RemoveLiquiditySingleSide(_) => "remove_liquidity_single_side",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            PoolInstruction::Swap(_) => SWAP_TOKEN_ACCOUNTS,
+            PoolInstruction::AddBalanceLiquidity(_)
+            | PoolInstruction::RemoveBalanceLiquidity(_)
+            | PoolInstruction::AddImbalanceLiquidity(_) => LIQUIDITY_TOKEN_ACCOUNTS,
+            PoolInstruction::RemoveLiquiditySingleSide(_) => SINGLE_SIDE_TOKEN_ACCOUNTS,
+        }
+    }
+
+    // Position of the signing `user` account in the instruction accounts
+    pub fn authority(&self) -> usize {
+        match self {
+            PoolInstruction::Swap(_) | PoolInstruction::RemoveLiquiditySingleSide(_) => 12,
+            PoolInstruction::AddBalanceLiquidity(_)
+            | PoolInstruction::RemoveBalanceLiquidity(_)
+            | PoolInstruction::AddImbalanceLiquidity(_) => 13,
+        }
+    }
+}
+
+// Decode a DAMM v1 instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<PoolInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DAMM v1 instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<PoolInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == SWAP => SwapArgs::deserialize(&mut args).ok().map(PoolInstruction::Swap),
+        d if d == ADD_BALANCE_LIQUIDITY => BalanceLiquidityArgs::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::AddBalanceLiquidity),
+        d if d == REMOVE_BALANCE_LIQUIDITY => BalanceLiquidityArgs::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::RemoveBalanceLiquidity),
+        d if d == ADD_IMBALANCE_LIQUIDITY => ImbalanceLiquidityArgs::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::AddImbalanceLiquidity),
+        d if d == REMOVE_LIQUIDITY_SINGLE_SIDE => SingleSideArgs::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::RemoveLiquiditySingleSide),
+        _ => None,
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapEvent {
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub trade_fee: u64,
+    pub protocol_fee: u64,
+    pub host_fee: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct PoolCreatedEvent {
+    pub lp_mint: [u8; 32],
+    pub token_a_mint: [u8; 32],
+    pub token_b_mint: [u8; 32],
+    // 0: permissioned, 1: permissionless
+    pub pool_type: u8,
+    pub pool: [u8; 32],
+}
+
+pub enum PoolEvent {
+    Swap(SwapEvent),
+    PoolCreated(PoolCreatedEvent),
+}
+
+// Decode a DAMM v1 event with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_event(idl_version: u32, data: &[u8]) -> Option<PoolEvent> {
+    match idl_version {
+        1 => decode_event_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DAMM v1 Anchor event from a "Program data:" log payload
+fn decode_event_v1(data: &[u8]) -> Option<PoolEvent> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut fields) = data.split_at(8);
+
+    match discriminator {
+        d if d == SWAP_EVENT => SwapEvent::deserialize(&mut fields).ok().map(PoolEvent::Swap),
+        d if d == POOL_CREATED_EVENT => PoolCreatedEvent::deserialize(&mut fields)
+            .ok()
+            .map(PoolEvent::PoolCreated),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator, event_payload, instruction_data};
+
+    #[test]
+    fn undecoded_instructions_are_idl_instructions() {
+        for (name, expected) in UNDECODED_INSTRUCTIONS {
+            assert_eq!(&discriminator("global", name), expected, "{}", name);
+            assert!(!INSTRUCTIONS.contains(expected), "{} is decoded", name);
+        }
+    }
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, PoolInstruction::name);
+        assert_eq!(discriminator("event", "Swap"), SWAP_EVENT);
+        assert_eq!(discriminator("event", "PoolCreated"), POOL_CREATED_EVENT);
+    }
+
+    #[test]
+    fn decodes_liquidity_args() {
+        let data = instruction_data("add_imbalance_liquidity", (10u64, 20u64, 30u64));
+        let Some(PoolInstruction::AddImbalanceLiquidity(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.minimum_pool_token_amount, args.token_a_amount, args.token_b_amount), (10, 20, 30));
+
+        let data = instruction_data("remove_liquidity_single_side", (40u64, 50u64));
+        let Some(PoolInstruction::RemoveLiquiditySingleSide(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.pool_token_amount, args.minimum_out_amount), (40, 50));
+    }
+
+    #[test]
+    fn decodes_logged_events() {
+        let Some(PoolEvent::Swap(event)) = decode_event(1, &event_payload("Swap", (1_000u64, 990u64, 3u64, 1u64, 0u64))) else {
+            panic!()
+        };
+        assert_eq!((event.in_amount, event.out_amount, event.trade_fee, event.protocol_fee), (1_000, 990, 3, 1));
+
+        let fields = ([1u8; 32], [2u8; 32], [3u8; 32], 1u8, [4u8; 32]);
+        let Some(PoolEvent::PoolCreated(event)) = decode_event(1, &event_payload("PoolCreated", fields)) else { panic!() };
+        assert_eq!((event.lp_mint, event.token_a_mint, event.token_b_mint), ([1; 32], [2; 32], [3; 32]));
+        assert_eq!((event.pool_type, event.pool), (1, [4; 32]));
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let data = instruction_data("swap", (1u64, 0u64));
+        assert!(decode_instruction(1, &data[..12]).is_none());
+        assert!(decode_event(1, &SWAP_EVENT).is_none());
+    }
+}
@@ -0,0 +1,366 @@
+use borsh::BorshDeserialize;
+
+use crate::EVENT_IX_TAG;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const ADD_LIQUIDITY: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+const REMOVE_LIQUIDITY: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+const REMOVE_ALL_LIQUIDITY: [u8; 8] = [10, 51, 61, 35, 112, 105, 24, 85];
+const CREATE_POSITION: [u8; 8] = [48, 215, 197, 153, 96, 203, 180, 133];
+const INITIALIZE_POOL: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+const INITIALIZE_POOL_WITH_DYNAMIC_CONFIG: [u8; 8] = [149, 82, 72, 197, 253, 252, 68, 15];
+const INITIALIZE_CUSTOMIZABLE_POOL: [u8; 8] = [20, 161, 241, 24, 189, 221, 180, 2];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    SWAP,
+    ADD_LIQUIDITY,
+    REMOVE_LIQUIDITY,
+    REMOVE_ALL_LIQUIDITY,
+    CREATE_POSITION,
+    INITIALIZE_POOL,
+    INITIALIZE_POOL_WITH_DYNAMIC_CONFIG,
+    INITIALIZE_CUSTOMIZABLE_POOL,
+];
+
+// IDL instructions the decoder leaves undecoded, so they are not reported as unknown discriminators
+pub const UNDECODED_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("swap2", [65, 75, 63, 76, 235, 91, 91, 136]),
+    ("claim_position_fee", [180, 38, 154, 17, 133, 33, 162, 211]),
+    ("claim_partner_fee", [97, 206, 39, 105, 94, 94, 126, 148]),
+    ("claim_protocol_fee", [165, 228, 133, 48, 99, 249, 255, 33]),
+    ("claim_reward", [149, 95, 181, 242, 94, 90, 158, 162]),
+    ("initialize_reward", [95, 135, 192, 196, 242, 129, 230, 68]),
+    ("fund_reward", [188, 50, 249, 165, 93, 151, 38, 63]),
+    ("update_reward_duration", [138, 174, 196, 169, 213, 235, 254, 107]),
+    ("update_reward_funder", [211, 28, 48, 32, 215, 160, 35, 23]),
+    ("withdraw_ineligible_reward", [148, 206, 42, 195, 247, 49, 103, 8]),
+    ("close_position", [123, 134, 81, 0, 49, 68, 98, 98]),
+    ("lock_position", [227, 62, 2, 252, 247, 10, 171, 185]),
+    ("permanent_lock_position", [165, 176, 125, 6, 231, 171, 186, 213]),
+    ("refresh_vesting", [9, 94, 216, 14, 116, 204, 247, 0]),
+    ("split_position", [172, 241, 221, 138, 161, 29, 253, 42]),
+    ("split_position2", [221, 147, 228, 207, 140, 212, 17, 119]),
+    ("set_pool_status", [112, 87, 135, 223, 83, 204, 132, 53]),
+    ("update_pool_fees", [118, 217, 203, 179, 60, 8, 70, 89]),
+    ("create_config", [201, 207, 243, 114, 75, 111, 47, 189]),
+    ("create_dynamic_config", [81, 251, 122, 78, 66, 57, 208, 82]),
+    ("close_config", [145, 9, 72, 157, 95, 125, 61, 85]),
+    ("create_token_badge", [88, 206, 0, 91, 60, 175, 151, 118]),
+    ("close_token_badge", [108, 146, 86, 110, 179, 254, 10, 104]),
+    ("create_claim_fee_operator", [169, 62, 207, 107, 58, 187, 162, 109]),
+    ("close_claim_fee_operator", [38, 134, 82, 216, 95, 124, 17, 99]),
+];
+
+// Anchor event discriminators: sha256("event:<Name>")[..8]
+const SWAP_EVENT: [u8; 8] = [27, 60, 21, 213, 138, 170, 187, 147];
+const INITIALIZE_POOL_EVENT: [u8; 8] = [228, 50, 246, 85, 203, 66, 134, 37];
+
+// Event discriminators known to the decoder
+pub const EVENTS: &[[u8; 8]] = &[
+    SWAP_EVENT,
+    INITIALIZE_POOL_EVENT,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const SWAP_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (2, "input_token_account"),
+    (3, "output_token_account"),
+    (4, "token_a_vault"),
+    (5, "token_b_vault"),
+    (11, "referral_token_account"),
+];
+const ADD_LIQUIDITY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (2, "token_a_account"),
+    (3, "token_b_account"),
+    (4, "token_a_vault"),
+    (5, "token_b_vault"),
+    (8, "position_nft_account"),
+];
+const REMOVE_LIQUIDITY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (3, "token_a_account"),
+    (4, "token_b_account"),
+    (5, "token_a_vault"),
+    (6, "token_b_vault"),
+    (9, "position_nft_account"),
+];
+const CREATE_POSITION_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(2, "position_nft_account")];
+const INITIALIZE_POOL_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (7, "position_nft_account"),
+    (10, "token_a_vault"),
+    (11, "token_b_vault"),
+    (12, "payer_token_a"),
+    (13, "payer_token_b"),
+];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapParameters {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityParameters {
+    pub liquidity_delta: u128,
+    pub token_a_amount_threshold: u64,
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RemoveAllLiquidityArgs {
+    pub token_a_amount_threshold: u64,
+    pub token_b_amount_threshold: u64,
+}
+
+#[derive(Debug)]
+pub enum PoolInstruction {
+    Swap(SwapParameters),
+    AddLiquidity(LiquidityParameters),
+    RemoveLiquidity(LiquidityParameters),
+    RemoveAllLiquidity(RemoveAllLiquidityArgs),
+    CreatePosition,
+    InitializePool,
+    InitializePoolWithDynamicConfig,
+    InitializeCustomizablePool,
+}
+
+impl PoolInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PoolInstruction::Swap(_) => "swap",
+            PoolInstruction::AddLiquidity(_) => "add_liquidity",
+            PoolInstruction::RemoveLiquidity(_) => "remove_liquidity",
+            PoolInstruction::RemoveAllLiquidity(_) => "remove_all_liquidity",
+            PoolInstruction::CreatePosition => "create_position",
+            PoolInstruction::InitializePool => "initialize_pool",
+            PoolInstruction::InitializePoolWithDynamicConfig => "initialize_pool_with_dynamic_config",
+            PoolInstruction::InitializeCustomizablePool => "initialize_customizable_pool",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            PoolInstruction::Swap(_) => SWAP_TOKEN_ACCOUNTS,
+            PoolInstruction::AddLiquidity(_) => ADD_LIQUIDITY_TOKEN_ACCOUNTS,
+            PoolInstruction::RemoveLiquidity(_) | PoolInstruction::RemoveAllLiquidity(_) => {
+                REMOVE_LIQUIDITY_TOKEN_ACCOUNTS
+            }
+            PoolInstruction::CreatePosition => CREATE_POSITION_TOKEN_ACCOUNTS,
+            PoolInstruction::InitializePool => INITIALIZE_POOL_TOKEN_ACCOUNTS,
+            PoolInstruction::InitializePoolWithDynamicConfig | PoolInstruction::InitializeCustomizablePool => &[],
+        }
+    }
+
+    // Position of the signing `payer` or `owner` account in the instruction accounts
+    pub fn authority(&self) -> usize {
+        match self {
+            PoolInstruction::Swap(_) => 8,
+            PoolInstruction::AddLiquidity(_) => 9,
+            PoolInstruction::RemoveLiquidity(_) | PoolInstruction::RemoveAllLiquidity(_) => 10,
+            PoolInstruction::CreatePosition => 6,
+            PoolInstruction::InitializePool => 1,
+            PoolInstruction::InitializePoolWithDynamicConfig | PoolInstruction::InitializeCustomizablePool => 3,
+        }
+    }
+
+    // Position of the `pool` account
+    pub fn pool(&self) -> usize {
+        match self {
+            PoolInstruction::AddLiquidity(_) => 0,
+            PoolInstruction::Swap(_) | PoolInstruction::RemoveLiquidity(_) | PoolInstruction::RemoveAllLiquidity(_) => 1,
+            PoolInstruction::CreatePosition => 3,
+            PoolInstruction::InitializePool => 4,
+            PoolInstruction::InitializePoolWithDynamicConfig => 7,
+            PoolInstruction::InitializeCustomizablePool => 5,
+        }
+    }
+
+    // Position of the `position` account, for instructions acting on a position
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            PoolInstruction::Swap(_) => None,
+            PoolInstruction::AddLiquidity(_) => Some(1),
+            PoolInstruction::RemoveLiquidity(_) | PoolInstruction::RemoveAllLiquidity(_) => Some(2),
+            PoolInstruction::CreatePosition => Some(4),
+            PoolInstruction::InitializePool => Some(5),
+            PoolInstruction::InitializePoolWithDynamicConfig => Some(8),
+            PoolInstruction::InitializeCustomizablePool => Some(6),
+        }
+    }
+
+    // Position of the `config` account, for pool initializations from a config
+    pub fn config(&self) -> Option<usize> {
+        match self {
+            PoolInstruction::InitializePool => Some(2),
+            PoolInstruction::InitializePoolWithDynamicConfig => Some(5),
+            _ => None,
+        }
+    }
+}
+
+// Decode a DAMM v2 instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<PoolInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DAMM v2 instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<PoolInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == SWAP => SwapParameters::deserialize(&mut args).ok().map(PoolInstruction::Swap),
+        d if d == ADD_LIQUIDITY => LiquidityParameters::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::AddLiquidity),
+        d if d == REMOVE_LIQUIDITY => LiquidityParameters::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::RemoveLiquidity),
+        d if d == REMOVE_ALL_LIQUIDITY => RemoveAllLiquidityArgs::deserialize(&mut args)
+            .ok()
+            .map(PoolInstruction::RemoveAllLiquidity),
+        d if d == CREATE_POSITION => Some(PoolInstruction::CreatePosition),
+        d if d == INITIALIZE_POOL => Some(PoolInstruction::InitializePool),
+        d if d == INITIALIZE_POOL_WITH_DYNAMIC_CONFIG => Some(PoolInstruction::InitializePoolWithDynamicConfig),
+        d if d == INITIALIZE_CUSTOMIZABLE_POOL => Some(PoolInstruction::InitializeCustomizablePool),
+        _ => None,
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapResult {
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub partner_fee: u64,
+    pub referral_fee: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapEvent {
+    pub pool: [u8; 32],
+    // 0: token A to token B, 1: token B to token A
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    pub params: SwapParameters,
+    pub swap_result: SwapResult,
+    pub actual_amount_in: u64,
+    pub current_timestamp: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct BaseFeeParameters {
+    // Fee numerator over 1_000_000_000 before any fee scheduler reduction
+    pub cliff_fee_numerator: u64,
+    pub number_of_period: u16,
+    pub period_frequency: u64,
+    pub reduction_factor: u64,
+    pub fee_scheduler_mode: u8,
+}
+
+// Leading fields of `EvtInitializePool`; the remaining pool parameters are not decoded
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializePoolEvent {
+    pub pool: [u8; 32],
+    pub token_a_mint: [u8; 32],
+    pub token_b_mint: [u8; 32],
+    pub creator: [u8; 32],
+    pub payer: [u8; 32],
+    pub alpha_vault_type: u8,
+    pub base_fee: BaseFeeParameters,
+}
+
+pub enum PoolEvent {
+    Swap(SwapEvent),
+    InitializePool(InitializePoolEvent),
+}
+
+// Decode a DAMM v2 event with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_event(idl_version: u32, data: &[u8]) -> Option<PoolEvent> {
+    match idl_version {
+        1 => decode_event_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DAMM v2 Anchor event from the data of an `emit_cpi!` self-invocation
+fn decode_event_v1(data: &[u8]) -> Option<PoolEvent> {
+    if data.len() < 16 || data[..8] != EVENT_IX_TAG {
+        return None;
+    }
+    let (discriminator, mut fields) = data[8..].split_at(8);
+
+    match discriminator {
+        d if d == SWAP_EVENT => SwapEvent::deserialize(&mut fields).ok().map(PoolEvent::Swap),
+        d if d == INITIALIZE_POOL_EVENT => InitializePoolEvent::deserialize(&mut fields)
+            .ok()
+            .map(PoolEvent::InitializePool),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator, event_data, instruction_data};
+
+    #[test]
+    fn undecoded_instructions_are_idl_instructions() {
+        for (name, expected) in UNDECODED_INSTRUCTIONS {
+            assert_eq!(&discriminator("global", name), expected, "{}", name);
+            assert!(!INSTRUCTIONS.contains(expected), "{} is decoded", name);
+        }
+    }
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, PoolInstruction::name);
+        assert_eq!(discriminator("event", "EvtSwap"), SWAP_EVENT);
+        assert_eq!(discriminator("event", "EvtInitializePool"), INITIALIZE_POOL_EVENT);
+    }
+
+    #[test]
+    fn decodes_liquidity_args() {
+        let data = instruction_data("add_liquidity", (1u128 << 70, 20u64, 30u64));
+        let Some(PoolInstruction::AddLiquidity(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!(
+            (args.liquidity_delta, args.token_a_amount_threshold, args.token_b_amount_threshold),
+            (1 << 70, 20, 30)
+        );
+    }
+
+    #[test]
+    fn decodes_swap_event() {
+        let swap_result = (990u64, 1u128 << 64, 3u64, 1u64, 0u64, 0u64);
+        let fields = ([1u8; 32], 1u8, false, (1_000u64, 900u64), swap_result, 1_000u64, 1_700_000_000u64);
+        let Some(PoolEvent::Swap(event)) = decode_event(1, &event_data("EvtSwap", fields)) else { panic!() };
+        assert_eq!((event.pool, event.trade_direction, event.has_referral), ([1; 32], 1, false));
+        assert_eq!((event.params.amount_in, event.params.minimum_amount_out), (1_000, 900));
+        assert_eq!((event.swap_result.output_amount, event.swap_result.next_sqrt_price), (990, 1 << 64));
+        assert_eq!((event.swap_result.lp_fee, event.swap_result.protocol_fee), (3, 1));
+        assert_eq!((event.actual_amount_in, event.current_timestamp), (1_000, 1_700_000_000));
+    }
+
+    #[test]
+    fn decodes_leading_initialize_pool_fields() {
+        let base_fee = (2_500_000u64, 0u16, 0u64, 0u64, 0u8);
+        let fields = ([1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32], [5u8; 32], 0u8, base_fee, [0u8; 64]);
+        let Some(PoolEvent::InitializePool(event)) = decode_event(1, &event_data("EvtInitializePool", fields)) else {
+            panic!()
+        };
+        assert_eq!((event.pool, event.token_a_mint, event.token_b_mint), ([1; 32], [2; 32], [3; 32]));
+        assert_eq!(event.base_fee.cliff_fee_numerator, 2_500_000);
+    }
+
+    #[test]
+    fn rejects_data_without_event_tag() {
+        assert!(decode_event(1, &crate::test_utils::event_payload("EvtSwap", [0u8; 256])).is_none());
+    }
+}
@@ -0,0 +1,253 @@
+use borsh::BorshDeserialize;
+
+use crate::EVENT_IX_TAG;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN: [u8; 8] = [140, 85, 215, 176, 102, 54, 104, 79];
+const INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022: [u8; 8] = [169, 118, 51, 78, 145, 110, 220, 155];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    SWAP,
+    INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN,
+    INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022,
+];
+
+// IDL instructions the decoder leaves undecoded, so they are not reported as unknown discriminators
+pub const UNDECODED_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("swap2", [65, 75, 63, 76, 235, 91, 91, 136]),
+    ("create_config", [201, 207, 243, 114, 75, 111, 47, 189]),
+    ("create_partner_metadata", [192, 168, 234, 191, 188, 226, 227, 255]),
+    ("create_virtual_pool_metadata", [45, 97, 187, 103, 254, 109, 124, 134]),
+    ("claim_trading_fee", [8, 236, 89, 49, 152, 125, 177, 81]),
+    ("claim_creator_trading_fee", [82, 220, 250, 189, 3, 85, 107, 45]),
+    ("claim_protocol_fee", [165, 228, 133, 48, 99, 249, 255, 33]),
+    ("create_claim_fee_operator", [169, 62, 207, 107, 58, 187, 162, 109]),
+    ("close_claim_fee_operator", [38, 134, 82, 216, 95, 124, 17, 99]),
+    ("creator_withdraw_surplus", [165, 3, 137, 7, 28, 134, 76, 80]),
+    ("partner_withdraw_surplus", [168, 173, 72, 100, 201, 98, 38, 92]),
+    ("protocol_withdraw_surplus", [54, 136, 225, 138, 172, 182, 214, 167]),
+    ("withdraw_leftover", [20, 198, 202, 237, 235, 243, 183, 66]),
+    ("withdraw_migration_fee", [237, 142, 45, 23, 129, 6, 222, 162]),
+    ("transfer_pool_creator", [20, 7, 169, 33, 58, 147, 166, 33]),
+    ("create_locker", [167, 90, 137, 154, 75, 47, 17, 84]),
+    ("migrate_meteora_damm", [27, 1, 48, 22, 180, 63, 118, 217]),
+    ("migrate_meteora_damm_lock_lp_token", [177, 55, 238, 157, 251, 88, 165, 42]),
+    ("migrate_meteora_damm_claim_lp_token", [139, 133, 2, 30, 91, 145, 127, 154]),
+    ("migration_meteora_damm_create_metadata", [47, 94, 126, 115, 221, 226, 194, 133]),
+    ("migration_damm_v2", [156, 169, 230, 103, 53, 228, 80, 64]),
+    ("migration_damm_v2_create_metadata", [109, 189, 19, 36, 195, 183, 222, 82]),
+];
+
+// Anchor event discriminators: sha256("event:<Name>")[..8]
+const SWAP_EVENT: [u8; 8] = [27, 60, 21, 213, 138, 170, 187, 147];
+const INITIALIZE_POOL_EVENT: [u8; 8] = [228, 50, 246, 85, 203, 66, 134, 37];
+
+// Event discriminators known to the decoder
+pub const EVENTS: &[[u8; 8]] = &[
+    SWAP_EVENT,
+    INITIALIZE_POOL_EVENT,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const SWAP_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (3, "input_token_account"),
+    (4, "output_token_account"),
+    (5, "base_vault"),
+    (6, "quote_vault"),
+    (12, "referral_token_account"),
+];
+const INITIALIZE_VIRTUAL_POOL_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(6, "base_vault"), (7, "quote_vault")];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapParameters {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+}
+
+#[derive(Debug)]
+pub enum CurveInstruction {
+    Swap(SwapParameters),
+    InitializeVirtualPoolWithSplToken,
+    InitializeVirtualPoolWithToken2022,
+}
+
+impl CurveInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CurveInstruction::Swap(_) => "swap",
+            CurveInstruction::InitializeVirtualPoolWithSplToken => "initialize_virtual_pool_with_spl_token",
+            CurveInstruction::InitializeVirtualPoolWithToken2022 => "initialize_virtual_pool_with_token2022",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            CurveInstruction::Swap(_) => SWAP_TOKEN_ACCOUNTS,
+            CurveInstruction::InitializeVirtualPoolWithSplToken | CurveInstruction::InitializeVirtualPoolWithToken2022 => {
+                INITIALIZE_VIRTUAL_POOL_TOKEN_ACCOUNTS
+            }
+        }
+    }
+
+    // Position of the signing `payer` account in the instruction accounts
+    pub fn authority(&self) -> usize {
+        match self {
+            CurveInstruction::Swap(_) => 9,
+            CurveInstruction::InitializeVirtualPoolWithSplToken => 10,
+            CurveInstruction::InitializeVirtualPoolWithToken2022 => 8,
+        }
+    }
+
+    // Position of the `pool` account
+    pub fn pool(&self) -> usize {
+        match self {
+            CurveInstruction::Swap(_) => 2,
+            CurveInstruction::InitializeVirtualPoolWithSplToken | CurveInstruction::InitializeVirtualPoolWithToken2022 => 5,
+        }
+    }
+
+    // Position of the `quote_mint` account, for pool initializations
+    pub fn quote_mint(&self) -> Option<usize> {
+        match self {
+            CurveInstruction::Swap(_) => None,
+            CurveInstruction::InitializeVirtualPoolWithSplToken | CurveInstruction::InitializeVirtualPoolWithToken2022 => Some(4),
+        }
+    }
+}
+
+// Decode a Dynamic Bonding Curve instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<CurveInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Dynamic Bonding Curve instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<CurveInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == SWAP => SwapParameters::deserialize(&mut args).ok().map(CurveInstruction::Swap),
+        d if d == INITIALIZE_VIRTUAL_POOL_WITH_SPL_TOKEN => Some(CurveInstruction::InitializeVirtualPoolWithSplToken),
+        d if d == INITIALIZE_VIRTUAL_POOL_WITH_TOKEN2022 => Some(CurveInstruction::InitializeVirtualPoolWithToken2022),
+        _ => None,
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapResult {
+    pub actual_input_amount: u64,
+    pub output_amount: u64,
+    pub next_sqrt_price: u128,
+    pub trading_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapEvent {
+    pub pool: [u8; 32],
+    pub config: [u8; 32],
+    // 0: base to quote, 1: quote to base
+    pub trade_direction: u8,
+    pub has_referral: bool,
+    pub params: SwapParameters,
+    pub swap_result: SwapResult,
+    pub amount_in: u64,
+    pub current_timestamp: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializePoolEvent {
+    pub pool: [u8; 32],
+    pub config: [u8; 32],
+    pub creator: [u8; 32],
+    pub base_mint: [u8; 32],
+    pub pool_type: u8,
+    pub activation_point: u64,
+}
+
+pub enum CurveEvent {
+    Swap(SwapEvent),
+    InitializePool(InitializePoolEvent),
+}
+
+// Decode a Dynamic Bonding Curve event with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_event(idl_version: u32, data: &[u8]) -> Option<CurveEvent> {
+    match idl_version {
+        1 => decode_event_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Dynamic Bonding Curve Anchor event from the data of an `emit_cpi!` self-invocation
+fn decode_event_v1(data: &[u8]) -> Option<CurveEvent> {
+    if data.len() < 16 || data[..8] != EVENT_IX_TAG {
+        return None;
+    }
+    let (discriminator, mut fields) = data[8..].split_at(8);
+
+    match discriminator {
+        d if d == SWAP_EVENT => SwapEvent::deserialize(&mut fields).ok().map(CurveEvent::Swap),
+        d if d == INITIALIZE_POOL_EVENT => InitializePoolEvent::deserialize(&mut fields)
+            .ok()
+            .map(CurveEvent::InitializePool),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator, event_data, instruction_data};
+
+    #[test]
+    fn undecoded_instructions_are_idl_instructions() {
+        for (name, expected) in UNDECODED_INSTRUCTIONS {
+            assert_eq!(&discriminator("global", name), expected, "{}", name);
+            assert!(!INSTRUCTIONS.contains(expected), "{} is decoded", name);
+        }
+    }
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, CurveInstruction::name);
+        assert_eq!(discriminator("event", "EvtSwap"), SWAP_EVENT);
+        assert_eq!(discriminator("event", "EvtInitializePool"), INITIALIZE_POOL_EVENT);
+    }
+
+    #[test]
+    fn decodes_swap_args() {
+        let Some(CurveInstruction::Swap(args)) = decode_instruction(1, &instruction_data("swap", (500u64, 1u64))) else {
+            panic!()
+        };
+        assert_eq!((args.amount_in, args.minimum_amount_out), (500, 1));
+    }
+
+    #[test]
+    fn decodes_swap_event() {
+        let swap_result = (500u64, 12_345u64, 1u128 << 64, 5u64, 1u64, 0u64);
+        let fields = ([1u8; 32], [2u8; 32], 1u8, true, (500u64, 1u64), swap_result, 500u64, 1_700_000_000u64);
+        let Some(CurveEvent::Swap(event)) = decode_event(1, &event_data("EvtSwap", fields)) else { panic!() };
+        assert_eq!((event.pool, event.config, event.trade_direction, event.has_referral), ([1; 32], [2; 32], 1, true));
+        assert_eq!((event.swap_result.actual_input_amount, event.swap_result.output_amount), (500, 12_345));
+        assert_eq!((event.swap_result.trading_fee, event.swap_result.protocol_fee), (5, 1));
+        assert_eq!((event.amount_in, event.current_timestamp), (500, 1_700_000_000));
+    }
+
+    #[test]
+    fn decodes_initialize_pool_event() {
+        let fields = ([1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32], 1u8, 99u64);
+        let Some(CurveEvent::InitializePool(event)) = decode_event(1, &event_data("EvtInitializePool", fields)) else {
+            panic!()
+        };
+        assert_eq!((event.pool, event.creator, event.base_mint), ([1; 32], [3; 32], [4; 32]));
+        assert_eq!((event.pool_type, event.activation_point), (1, 99));
+    }
+}
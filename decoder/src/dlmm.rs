@@ -0,0 +1,764 @@
+use borsh::BorshDeserialize;
+
+use crate::EVENT_IX_TAG;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const INITIALIZE_LB_PAIR: [u8; 8] = [45, 154, 237, 210, 221, 15, 166, 92];
+const INITIALIZE_POSITION: [u8; 8] = [219, 192, 234, 71, 190, 191, 102, 80];
+const INITIALIZE_POSITION_PDA: [u8; 8] = [46, 82, 125, 146, 85, 141, 228, 153];
+const INITIALIZE_POSITION_BY_OPERATOR: [u8; 8] = [251, 189, 190, 244, 117, 254, 35, 148];
+const ADD_LIQUIDITY: [u8; 8] = [181, 157, 89, 67, 143, 182, 52, 72];
+const ADD_LIQUIDITY_BY_WEIGHT: [u8; 8] = [28, 140, 238, 99, 231, 162, 21, 149];
+const ADD_LIQUIDITY_BY_STRATEGY: [u8; 8] = [7, 3, 150, 127, 148, 40, 61, 200];
+const ADD_LIQUIDITY_ONE_SIDE: [u8; 8] = [94, 155, 103, 151, 70, 95, 220, 165];
+const ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE: [u8; 8] = [41, 5, 238, 175, 100, 225, 6, 205];
+const REMOVE_LIQUIDITY: [u8; 8] = [80, 85, 209, 72, 24, 206, 177, 108];
+const REMOVE_LIQUIDITY_BY_RANGE: [u8; 8] = [26, 82, 102, 152, 240, 74, 105, 26];
+const REMOVE_ALL_LIQUIDITY: [u8; 8] = [10, 51, 61, 35, 112, 105, 24, 85];
+const CLAIM_FEE: [u8; 8] = [169, 32, 79, 137, 136, 232, 70, 137];
+const CLAIM_REWARD: [u8; 8] = [149, 95, 181, 242, 94, 90, 158, 162];
+const CLOSE_POSITION: [u8; 8] = [123, 134, 81, 0, 49, 68, 98, 98];
+const SWAP: [u8; 8] = [248, 198, 158, 145, 225, 117, 135, 200];
+const SWAP_EXACT_OUT: [u8; 8] = [250, 73, 101, 33, 38, 207, 75, 184];
+const SWAP_WITH_PRICE_IMPACT: [u8; 8] = [56, 173, 230, 208, 173, 228, 156, 205];
+const SWAP2: [u8; 8] = [65, 75, 63, 76, 235, 91, 91, 136];
+const SWAP_EXACT_OUT2: [u8; 8] = [43, 215, 247, 132, 137, 60, 243, 81];
+const SWAP_WITH_PRICE_IMPACT2: [u8; 8] = [74, 98, 192, 214, 177, 51, 75, 51];
+const INITIALIZE_PRESET_PARAMETER: [u8; 8] = [66, 188, 71, 211, 98, 109, 14, 186];
+const UPDATE_FEE_PARAMETERS: [u8; 8] = [128, 128, 208, 91, 246, 53, 31, 176];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    INITIALIZE_LB_PAIR,
+    INITIALIZE_POSITION,
+    INITIALIZE_POSITION_PDA,
+    INITIALIZE_POSITION_BY_OPERATOR,
+    ADD_LIQUIDITY,
+    ADD_LIQUIDITY_BY_WEIGHT,
+    ADD_LIQUIDITY_BY_STRATEGY,
+    ADD_LIQUIDITY_ONE_SIDE,
+    ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE,
+    REMOVE_LIQUIDITY,
+    REMOVE_LIQUIDITY_BY_RANGE,
+    REMOVE_ALL_LIQUIDITY,
+    CLAIM_FEE,
+    CLAIM_REWARD,
+    CLOSE_POSITION,
+    SWAP,
+    SWAP_EXACT_OUT,
+    SWAP_WITH_PRICE_IMPACT,
+    SWAP2,
+    SWAP_EXACT_OUT2,
+    SWAP_WITH_PRICE_IMPACT2,
+    INITIALIZE_PRESET_PARAMETER,
+    UPDATE_FEE_PARAMETERS,
+];
+
+// IDL instructions the decoder leaves undecoded, so they are not reported as unknown discriminators
+pub const UNDECODED_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("initialize_permission_lb_pair", [108, 102, 213, 85, 251, 3, 53, 21]),
+    ("initialize_customizable_permissionless_lb_pair", [46, 39, 41, 135, 111, 183, 200, 64]),
+    ("initialize_lb_pair2", [73, 59, 36, 120, 237, 83, 108, 198]),
+    ("initialize_customizable_permissionless_lb_pair2", [243, 73, 129, 126, 51, 19, 241, 107]),
+    ("initialize_bin_array_bitmap_extension", [47, 157, 226, 180, 12, 240, 33, 71]),
+    ("initialize_bin_array", [35, 86, 19, 185, 78, 212, 75, 211]),
+    ("add_liquidity_one_side_precise", [161, 194, 103, 84, 171, 71, 250, 154]),
+    ("add_liquidity2", [228, 162, 78, 28, 70, 219, 116, 115]),
+    ("add_liquidity_by_strategy2", [3, 221, 149, 218, 111, 141, 118, 213]),
+    ("add_liquidity_one_side_precise2", [33, 51, 163, 201, 117, 98, 125, 231]),
+    ("remove_liquidity2", [230, 215, 82, 127, 241, 101, 227, 146]),
+    ("remove_liquidity_by_range2", [204, 2, 195, 145, 53, 145, 145, 205]),
+    ("claim_fee2", [112, 191, 101, 171, 28, 144, 127, 187]),
+    ("claim_reward2", [190, 3, 127, 119, 178, 87, 157, 183]),
+    ("close_position2", [174, 90, 35, 115, 186, 40, 147, 226]),
+    ("close_position_if_empty", [59, 124, 212, 118, 91, 152, 110, 157]),
+    ("update_fees_and_rewards", [154, 230, 250, 13, 236, 209, 75, 223]),
+    ("update_fees_and_reward2", [32, 142, 184, 154, 103, 65, 184, 88]),
+    ("go_to_a_bin", [146, 72, 174, 224, 40, 253, 84, 174]),
+    ("withdraw_protocol_fee", [158, 201, 158, 189, 33, 93, 162, 103]),
+    ("initialize_reward", [95, 135, 192, 196, 242, 129, 230, 68]),
+    ("fund_reward", [188, 50, 249, 165, 93, 151, 38, 63]),
+    ("update_reward_funder", [211, 28, 48, 32, 215, 160, 35, 23]),
+    ("update_reward_duration", [138, 174, 196, 169, 213, 235, 254, 107]),
+    ("withdraw_ineligible_reward", [148, 206, 42, 195, 247, 49, 103, 8]),
+    ("update_position_operator", [202, 184, 103, 143, 180, 191, 116, 217]),
+    ("set_activation_point", [91, 249, 15, 165, 26, 129, 254, 125]),
+    ("set_pre_activation_duration", [165, 61, 201, 244, 130, 159, 22, 100]),
+    ("set_pre_activation_swap_address", [57, 139, 47, 123, 216, 80, 223, 10]),
+    ("set_pair_status", [67, 248, 231, 137, 154, 149, 217, 174]),
+    ("set_pair_status_permissionless", [78, 59, 152, 211, 70, 183, 46, 208]),
+    ("migrate_position", [15, 132, 59, 50, 199, 6, 251, 46]),
+    ("migrate_bin_array", [17, 23, 159, 211, 101, 184, 41, 241]),
+    ("increase_oracle_length", [190, 61, 125, 87, 103, 79, 158, 173]),
+    ("initialize_preset_parameter2", [184, 7, 240, 171, 103, 47, 183, 121]),
+    ("close_preset_parameter", [4, 148, 145, 100, 134, 26, 181, 61]),
+    ("close_preset_parameter2", [39, 25, 95, 107, 116, 17, 115, 28]),
+    ("initialize_token_badge", [253, 77, 205, 95, 27, 224, 89, 223]),
+    ("create_claim_protocol_fee_operator", [51, 19, 150, 252, 105, 157, 48, 91]),
+    ("close_claim_protocol_fee_operator", [8, 41, 87, 35, 80, 48, 121, 26]),
+    ("increase_position_length", [80, 83, 117, 211, 66, 13, 33, 149]),
+    ("decrease_position_length", [194, 219, 136, 32, 25, 96, 105, 37]),
+    ("rebalance_liquidity", [92, 4, 176, 193, 119, 185, 83, 9]),
+    ("update_base_fee_parameters", [75, 168, 223, 161, 16, 195, 3, 47]),
+    ("update_dynamic_fee_parameters", [92, 161, 46, 246, 255, 189, 22, 22]),
+];
+
+// Anchor event discriminators: sha256("event:<Name>")[..8]
+const POSITION_CREATE_EVENT: [u8; 8] = [144, 142, 252, 84, 157, 53, 37, 121];
+const POSITION_CLOSE_EVENT: [u8; 8] = [255, 196, 16, 107, 28, 202, 53, 128];
+const ADD_LIQUIDITY_EVENT: [u8; 8] = [31, 94, 125, 90, 227, 52, 61, 186];
+const REMOVE_LIQUIDITY_EVENT: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
+const CLAIM_FEE_EVENT: [u8; 8] = [75, 122, 154, 48, 140, 74, 123, 163];
+const CLAIM_REWARD_EVENT: [u8; 8] = [148, 116, 134, 204, 22, 171, 85, 95];
+const SWAP_EVENT: [u8; 8] = [81, 108, 227, 190, 205, 208, 10, 196];
+const LB_PAIR_CREATE_EVENT: [u8; 8] = [185, 74, 252, 125, 27, 215, 188, 111];
+
+// Event discriminators known to the decoder
+pub const EVENTS: &[[u8; 8]] = &[
+    POSITION_CREATE_EVENT,
+    POSITION_CLOSE_EVENT,
+    ADD_LIQUIDITY_EVENT,
+    REMOVE_LIQUIDITY_EVENT,
+    CLAIM_FEE_EVENT,
+    CLAIM_REWARD_EVENT,
+    SWAP_EVENT,
+    LB_PAIR_CREATE_EVENT,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const INITIALIZE_LB_PAIR_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(4, "reserve_x"), (5, "reserve_y")];
+const LIQUIDITY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (3, "user_token_x"),
+    (4, "user_token_y"),
+    (5, "reserve_x"),
+    (6, "reserve_y"),
+];
+const ONE_SIDE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(3, "user_token"), (4, "reserve")];
+const CLAIM_FEE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (5, "reserve_x"),
+    (6, "reserve_y"),
+    (7, "user_token_x"),
+    (8, "user_token_y"),
+];
+const CLAIM_REWARD_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(5, "reward_vault"), (7, "user_token_account")];
+const SWAP_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (2, "reserve_x"),
+    (3, "reserve_y"),
+    (4, "user_token_in"),
+    (5, "user_token_out"),
+    (9, "host_fee_in"),
+];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializeLbPairArgs {
+    pub active_id: i32,
+    pub bin_step: u16,
+}
+
+// Fee and volatility parameters that pairs created from a preset start with
+#[derive(BorshDeserialize, Debug)]
+pub struct InitPresetParametersArgs {
+    pub bin_step: u16,
+    pub base_factor: u16,
+    pub filter_period: u16,
+    pub decay_period: u16,
+    pub reduction_factor: u16,
+    pub variable_fee_control: u32,
+    pub max_volatility_accumulator: u32,
+    pub min_bin_id: i32,
+    pub max_bin_id: i32,
+    pub protocol_share: u16,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct FeeParameterArgs {
+    pub protocol_share: u16,
+    pub base_factor: u16,
+}
+
+// Shared by initialize_position, initialize_position_pda and initialize_position_by_operator
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializePositionArgs {
+    pub lower_bin_id: i32,
+    pub width: i32,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct BinLiquidityDistribution {
+    pub bin_id: i32,
+    pub distribution_x: u16,
+    pub distribution_y: u16,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityParameter {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistribution>,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct BinLiquidityDistributionByWeight {
+    pub bin_id: i32,
+    pub weight: u16,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityParameterByWeight {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistributionByWeight>,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct StrategyParameters {
+    pub min_bin_id: i32,
+    pub max_bin_id: i32,
+    pub strategy_type: u8,
+    pub parameters: [u8; 64],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityParameterByStrategy {
+    pub amount_x: u64,
+    pub amount_y: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub strategy_parameters: StrategyParameters,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityOneSideParameter {
+    pub amount: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub bin_liquidity_dist: Vec<BinLiquidityDistributionByWeight>,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityParameterByStrategyOneSide {
+    pub amount: u64,
+    pub active_id: i32,
+    pub max_active_bin_slippage: i32,
+    pub strategy_parameters: StrategyParameters,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct BinLiquidityReduction {
+    pub bin_id: i32,
+    pub bps_to_remove: u16,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RemoveLiquidityArgs {
+    pub bin_liquidity_removal: Vec<BinLiquidityReduction>,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RemoveLiquidityByRangeArgs {
+    pub from_bin_id: i32,
+    pub to_bin_id: i32,
+    pub bps_to_remove: u16,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ClaimRewardArgs {
+    pub reward_index: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapArgs {
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapExactOutArgs {
+    pub max_in_amount: u64,
+    pub out_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapWithPriceImpactArgs {
+    pub amount_in: u64,
+    pub active_id: Option<i32>,
+    pub max_price_impact_bps: u16,
+}
+
+#[derive(Debug)]
+pub enum PairInstruction {
+    InitializeLbPair(InitializeLbPairArgs),
+    InitializePosition(InitializePositionArgs),
+    InitializePositionPda(InitializePositionArgs),
+    InitializePositionByOperator(InitializePositionArgs),
+    AddLiquidity(LiquidityParameter),
+    AddLiquidityByWeight(LiquidityParameterByWeight),
+    AddLiquidityByStrategy(LiquidityParameterByStrategy),
+    AddLiquidityOneSide(LiquidityOneSideParameter),
+    AddLiquidityByStrategyOneSide(LiquidityParameterByStrategyOneSide),
+    RemoveLiquidity(RemoveLiquidityArgs),
+    RemoveLiquidityByRange(RemoveLiquidityByRangeArgs),
+    RemoveAllLiquidity,
+    ClaimFee,
+    ClaimReward(ClaimRewardArgs),
+    ClosePosition,
+    Swap(SwapArgs),
+    SwapExactOut(SwapExactOutArgs),
+    SwapWithPriceImpact(SwapWithPriceImpactArgs),
+    // Token-2022 aware variants; their trailing `remaining_accounts_info` argument is not decoded
+    Swap2(SwapArgs),
+    SwapExactOut2(SwapExactOutArgs),
+    SwapWithPriceImpact2(SwapWithPriceImpactArgs),
+    InitializePresetParameter(InitPresetParametersArgs),
+    UpdateFeeParameters(FeeParameterArgs),
+}
+
+impl PairInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PairInstruction::InitializeLbPair(_) => "initialize_lb_pair",
+            PairInstruction::InitializePosition(_) => "initialize_position",
+            PairInstruction::InitializePositionPda(_) => "initialize_position_pda",
+            PairInstruction::InitializePositionByOperator(_) => "initialize_position_by_operator",
+            PairInstruction::AddLiquidity(_) => "add_liquidity",
+            PairInstruction::AddLiquidityByWeight(_) => "add_liquidity_by_weight",
+            PairInstruction::AddLiquidityByStrategy(_) => "add_liquidity_by_strategy",
+            PairInstruction::AddLiquidityOneSide(_) => "add_liquidity_one_side",
+            PairInstruction::AddLiquidityByStrategyOneSide(_) => "add_liquidity_by_strategy_one_side",
+            PairInstruction::RemoveLiquidity(_) => "remove_liquidity",
+            PairInstruction::RemoveLiquidityByRange(_) => "remove_liquidity_by_range",
+            PairInstruction::RemoveAllLiquidity => "remove_all_liquidity",
+            PairInstruction::ClaimFee => "claim_fee",
+            PairInstruction::ClaimReward(_) => "claim_reward",
+            PairInstruction::ClosePosition => "close_position",
+            PairInstruction::Swap(_) => "swap",
+            PairInstruction::SwapExactOut(_) => "swap_exact_out",
+            PairInstruction::SwapWithPriceImpact(_) => "swap_with_price_impact",
+            PairInstruction::Swap2(_) => "swap2",
+            PairInstruction::SwapExactOut2(_) => "swap_exact_out2",
+            PairInstruction::SwapWithPriceImpact2(_) => "swap_with_price_impact2",
+            PairInstruction::InitializePresetParameter(_) => "initialize_preset_parameter",
+            PairInstruction::UpdateFeeParameters(_) => "update_fee_parameters",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            PairInstruction::InitializeLbPair(_) => INITIALIZE_LB_PAIR_TOKEN_ACCOUNTS,
+            PairInstruction::AddLiquidity(_)
+            | PairInstruction::AddLiquidityByWeight(_)
+            | PairInstruction::AddLiquidityByStrategy(_)
+            | PairInstruction::RemoveLiquidity(_)
+            | PairInstruction::RemoveLiquidityByRange(_)
+            | PairInstruction::RemoveAllLiquidity => LIQUIDITY_TOKEN_ACCOUNTS,
+            PairInstruction::AddLiquidityOneSide(_) | PairInstruction::AddLiquidityByStrategyOneSide(_) => {
+                ONE_SIDE_TOKEN_ACCOUNTS
+            }
+            PairInstruction::ClaimFee => CLAIM_FEE_TOKEN_ACCOUNTS,
+            PairInstruction::ClaimReward(_) => CLAIM_REWARD_TOKEN_ACCOUNTS,
+            PairInstruction::Swap(_)
+            | PairInstruction::SwapExactOut(_)
+            | PairInstruction::SwapWithPriceImpact(_)
+            | PairInstruction::Swap2(_)
+            | PairInstruction::SwapExactOut2(_)
+            | PairInstruction::SwapWithPriceImpact2(_) => SWAP_TOKEN_ACCOUNTS,
+            _ => &[],
+        }
+    }
+
+    // Position of the signing `funder`, `owner`, `operator`, `sender`, `user` or `admin` account
+    pub fn authority(&self) -> usize {
+        match self {
+            PairInstruction::InitializeLbPair(_) => 8,
+            PairInstruction::InitializePosition(_) => 3,
+            PairInstruction::InitializePositionPda(_) => 4,
+            PairInstruction::InitializePositionByOperator(_) => 5,
+            PairInstruction::AddLiquidity(_)
+            | PairInstruction::AddLiquidityByWeight(_)
+            | PairInstruction::AddLiquidityByStrategy(_)
+            | PairInstruction::RemoveLiquidity(_)
+            | PairInstruction::RemoveLiquidityByRange(_)
+            | PairInstruction::RemoveAllLiquidity => 11,
+            PairInstruction::AddLiquidityOneSide(_) | PairInstruction::AddLiquidityByStrategyOneSide(_) => 8,
+            PairInstruction::ClaimFee | PairInstruction::ClaimReward(_) | PairInstruction::ClosePosition => 4,
+            PairInstruction::Swap(_)
+            | PairInstruction::SwapExactOut(_)
+            | PairInstruction::SwapWithPriceImpact(_)
+            | PairInstruction::Swap2(_)
+            | PairInstruction::SwapExactOut2(_)
+            | PairInstruction::SwapWithPriceImpact2(_) => 10,
+            PairInstruction::InitializePresetParameter(_) | PairInstruction::UpdateFeeParameters(_) => 1,
+        }
+    }
+
+    pub fn is_swap(&self) -> bool {
+        matches!(
+            self,
+            PairInstruction::Swap(_)
+                | PairInstruction::SwapExactOut(_)
+                | PairInstruction::SwapWithPriceImpact(_)
+                | PairInstruction::Swap2(_)
+                | PairInstruction::SwapExactOut2(_)
+                | PairInstruction::SwapWithPriceImpact2(_)
+        )
+    }
+
+    // Position of the `lb_pair` account; `initialize_preset_parameter` has none and points at its preset
+    pub fn lb_pair(&self) -> usize {
+        match self {
+            PairInstruction::InitializeLbPair(_)
+            | PairInstruction::InitializePresetParameter(_)
+            | PairInstruction::UpdateFeeParameters(_)
+            | PairInstruction::ClaimFee
+            | PairInstruction::ClaimReward(_)
+            | PairInstruction::Swap(_)
+            | PairInstruction::SwapExactOut(_)
+            | PairInstruction::SwapWithPriceImpact(_)
+            | PairInstruction::Swap2(_)
+            | PairInstruction::SwapExactOut2(_)
+            | PairInstruction::SwapWithPriceImpact2(_) => 0,
+            PairInstruction::InitializePosition(_) => 2,
+            PairInstruction::InitializePositionPda(_) | PairInstruction::InitializePositionByOperator(_) => 3,
+            _ => 1,
+        }
+    }
+
+    // Position of the `position` account, for instructions acting on a position
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            PairInstruction::InitializeLbPair(_)
+            | PairInstruction::InitializePresetParameter(_)
+            | PairInstruction::UpdateFeeParameters(_)
+            | PairInstruction::Swap(_)
+            | PairInstruction::SwapExactOut(_)
+            | PairInstruction::SwapWithPriceImpact(_)
+            | PairInstruction::Swap2(_)
+            | PairInstruction::SwapExactOut2(_)
+            | PairInstruction::SwapWithPriceImpact2(_) => None,
+            PairInstruction::InitializePosition(_) | PairInstruction::ClaimFee | PairInstruction::ClaimReward(_) => Some(1),
+            PairInstruction::InitializePositionPda(_) | PairInstruction::InitializePositionByOperator(_) => Some(2),
+            _ => Some(0),
+        }
+    }
+}
+
+// Decode a DLMM instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<PairInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DLMM instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<PairInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == INITIALIZE_LB_PAIR => InitializeLbPairArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::InitializeLbPair),
+        d if d == INITIALIZE_POSITION => InitializePositionArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::InitializePosition),
+        d if d == INITIALIZE_POSITION_PDA => InitializePositionArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::InitializePositionPda),
+        d if d == INITIALIZE_POSITION_BY_OPERATOR => InitializePositionArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::InitializePositionByOperator),
+        d if d == ADD_LIQUIDITY => LiquidityParameter::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::AddLiquidity),
+        d if d == ADD_LIQUIDITY_BY_WEIGHT => LiquidityParameterByWeight::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::AddLiquidityByWeight),
+        d if d == ADD_LIQUIDITY_BY_STRATEGY => LiquidityParameterByStrategy::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::AddLiquidityByStrategy),
+        d if d == ADD_LIQUIDITY_ONE_SIDE => LiquidityOneSideParameter::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::AddLiquidityOneSide),
+        d if d == ADD_LIQUIDITY_BY_STRATEGY_ONE_SIDE => LiquidityParameterByStrategyOneSide::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::AddLiquidityByStrategyOneSide),
+        d if d == REMOVE_LIQUIDITY => RemoveLiquidityArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::RemoveLiquidity),
+        d if d == REMOVE_LIQUIDITY_BY_RANGE => RemoveLiquidityByRangeArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::RemoveLiquidityByRange),
+        d if d == REMOVE_ALL_LIQUIDITY => Some(PairInstruction::RemoveAllLiquidity),
+        d if d == CLAIM_FEE => Some(PairInstruction::ClaimFee),
+        d if d == CLAIM_REWARD => ClaimRewardArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::ClaimReward),
+        d if d == CLOSE_POSITION => Some(PairInstruction::ClosePosition),
+        d if d == SWAP => SwapArgs::deserialize(&mut args).ok().map(PairInstruction::Swap),
+        d if d == SWAP_EXACT_OUT => SwapExactOutArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::SwapExactOut),
+        d if d == SWAP_WITH_PRICE_IMPACT => SwapWithPriceImpactArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::SwapWithPriceImpact),
+        d if d == SWAP2 => SwapArgs::deserialize(&mut args).ok().map(PairInstruction::Swap2),
+        d if d == SWAP_EXACT_OUT2 => SwapExactOutArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::SwapExactOut2),
+        d if d == SWAP_WITH_PRICE_IMPACT2 => SwapWithPriceImpactArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::SwapWithPriceImpact2),
+        d if d == INITIALIZE_PRESET_PARAMETER => InitPresetParametersArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::InitializePresetParameter),
+        d if d == UPDATE_FEE_PARAMETERS => FeeParameterArgs::deserialize(&mut args)
+            .ok()
+            .map(PairInstruction::UpdateFeeParameters),
+        _ => None,
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct PositionCreateEvent {
+    pub lb_pair: [u8; 32],
+    pub position: [u8; 32],
+    pub owner: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct PositionCloseEvent {
+    pub position: [u8; 32],
+    pub owner: [u8; 32],
+}
+
+// Shared by the AddLiquidity and RemoveLiquidity events
+#[derive(BorshDeserialize, Debug)]
+pub struct LiquidityEvent {
+    pub lb_pair: [u8; 32],
+    pub from: [u8; 32],
+    pub position: [u8; 32],
+    pub amounts: [u64; 2],
+    pub active_bin_id: i32,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ClaimFeeEvent {
+    pub lb_pair: [u8; 32],
+    pub position: [u8; 32],
+    pub owner: [u8; 32],
+    pub fee_x: u64,
+    pub fee_y: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ClaimRewardEvent {
+    pub lb_pair: [u8; 32],
+    pub position: [u8; 32],
+    pub owner: [u8; 32],
+    pub reward_index: u64,
+    pub total_reward: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct SwapEvent {
+    pub lb_pair: [u8; 32],
+    pub from: [u8; 32],
+    pub start_bin_id: i32,
+    pub end_bin_id: i32,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub swap_for_y: bool,
+    pub fee: u64,
+    pub protocol_fee: u64,
+    pub fee_bps: u128,
+    pub host_fee: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct LbPairCreateEvent {
+    pub lb_pair: [u8; 32],
+    pub bin_step: u16,
+    pub token_x: [u8; 32],
+    pub token_y: [u8; 32],
+}
+
+pub enum PairEvent {
+    PositionCreate(PositionCreateEvent),
+    PositionClose(PositionCloseEvent),
+    AddLiquidity(LiquidityEvent),
+    RemoveLiquidity(LiquidityEvent),
+    ClaimFee(ClaimFeeEvent),
+    ClaimReward(ClaimRewardEvent),
+    Swap(SwapEvent),
+    LbPairCreate(LbPairCreateEvent),
+}
+
+// Decode a DLMM event with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_event(idl_version: u32, data: &[u8]) -> Option<PairEvent> {
+    match idl_version {
+        1 => decode_event_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a DLMM Anchor event from the data of an `emit_cpi!` self-invocation
+fn decode_event_v1(data: &[u8]) -> Option<PairEvent> {
+    if data.len() < 16 || data[..8] != EVENT_IX_TAG {
+        return None;
+    }
+    let (discriminator, mut fields) = data[8..].split_at(8);
+
+    match discriminator {
+        d if d == POSITION_CREATE_EVENT => PositionCreateEvent::deserialize(&mut fields)
+            .ok()
+            .map(PairEvent::PositionCreate),
+        d if d == POSITION_CLOSE_EVENT => PositionCloseEvent::deserialize(&mut fields)
+            .ok()
+            .map(PairEvent::PositionClose),
+        d if d == ADD_LIQUIDITY_EVENT => LiquidityEvent::deserialize(&mut fields).ok().map(PairEvent::AddLiquidity),
+        d if d == REMOVE_LIQUIDITY_EVENT => LiquidityEvent::deserialize(&mut fields)
+            .ok()
+            .map(PairEvent::RemoveLiquidity),
+        d if d == CLAIM_FEE_EVENT => ClaimFeeEvent::deserialize(&mut fields).ok().map(PairEvent::ClaimFee),
+        d if d == CLAIM_REWARD_EVENT => ClaimRewardEvent::deserialize(&mut fields).ok().map(PairEvent::ClaimReward),
+        d if d == SWAP_EVENT => SwapEvent::deserialize(&mut fields).ok().map(PairEvent::Swap),
+        d if d == LB_PAIR_CREATE_EVENT => LbPairCreateEvent::deserialize(&mut fields)
+            .ok()
+            .map(PairEvent::LbPairCreate),
+        _ => None,
+    }
+}
+
+// Fee rates are fractions of FEE_PRECISION
+const FEE_PRECISION: f64 = 1_000_000_000.0;
+
+// Base fee rate of a pair, as a fraction: base_factor * bin_step * 10 / FEE_PRECISION
+pub fn base_fee_rate(base_factor: u16, bin_step: u16) -> f64 {
+    base_factor as f64 * bin_step as f64 * 10.0 / FEE_PRECISION
+}
+
+// Volatility accumulator implied by a variable fee rate, inverting
+// variable_fee_control * (volatility_accumulator * bin_step) ^ 2 / 100_000_000_000 / FEE_PRECISION
+pub fn volatility_accumulator(variable_fee_rate: f64, variable_fee_control: u32, bin_step: u16) -> u32 {
+    if variable_fee_control == 0 || bin_step == 0 || variable_fee_rate <= 0.0 {
+        return 0;
+    }
+    let squared = variable_fee_rate * FEE_PRECISION * 100_000_000_000.0 / variable_fee_control as f64;
+    (squared.sqrt() / bin_step as f64).round() as u32
+}
+
+// Price of one lamport of token X in lamports of token Y at a bin: (1 + bin_step / 10_000) ^ bin_id
+pub fn bin_price(bin_id: i32, bin_step: u16) -> f64 {
+    (1.0 + bin_step as f64 / 10_000.0).powi(bin_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator, event_data, instruction_data};
+
+    #[test]
+    fn undecoded_instructions_are_idl_instructions() {
+        for (name, expected) in UNDECODED_INSTRUCTIONS {
+            assert_eq!(&discriminator("global", name), expected, "{}", name);
+            assert!(!INSTRUCTIONS.contains(expected), "{} is decoded", name);
+        }
+    }
+
+    #[test]
+    fn instruction_discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, PairInstruction::name);
+    }
+
+    #[test]
+    fn event_discriminators_match_their_names() {
+        for (expected, name) in [
+            (POSITION_CREATE_EVENT, "PositionCreate"),
+            (POSITION_CLOSE_EVENT, "PositionClose"),
+            (ADD_LIQUIDITY_EVENT, "AddLiquidity"),
+            (REMOVE_LIQUIDITY_EVENT, "RemoveLiquidity"),
+            (CLAIM_FEE_EVENT, "ClaimFee"),
+            (CLAIM_REWARD_EVENT, "ClaimReward"),
+            (SWAP_EVENT, "Swap"),
+            (LB_PAIR_CREATE_EVENT, "LbPairCreate"),
+        ] {
+            assert_eq!(discriminator("event", name), expected, "{}", name);
+        }
+        assert_eq!(EVENTS.len(), 8);
+    }
+
+    #[test]
+    fn decodes_swap_with_price_impact_args() {
+        let data = instruction_data("swap_with_price_impact2", (7_000u64, Some(-42i32), 150u16));
+        let Some(PairInstruction::SwapWithPriceImpact2(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.amount_in, args.active_id, args.max_price_impact_bps), (7_000, Some(-42), 150));
+    }
+
+    #[test]
+    fn decodes_add_liquidity_bin_distribution() {
+        let bins = vec![(-1i32, 0u16, 5_000u16), (0, 5_000, 5_000), (1, 5_000, 0)];
+        let data = instruction_data("add_liquidity", (1_000u64, 2_000u64, bins));
+        let Some(PairInstruction::AddLiquidity(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.amount_x, args.amount_y), (1_000, 2_000));
+        let decoded: Vec<_> = args
+            .bin_liquidity_dist
+            .iter()
+            .map(|bin| (bin.bin_id, bin.distribution_x, bin.distribution_y))
+            .collect();
+        assert_eq!(decoded, vec![(-1, 0, 5_000), (0, 5_000, 5_000), (1, 5_000, 0)]);
+    }
+
+    #[test]
+    fn decodes_preset_and_fee_parameters() {
+        let data = instruction_data(
+            "initialize_preset_parameter",
+            (10u16, 10_000u16, 30u16, 600u16, 5_000u16, 40_000u32, 350_000u32, -1_000i32, 1_000i32, 500u16),
+        );
+        let Some(PairInstruction::InitializePresetParameter(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.bin_step, args.base_factor, args.variable_fee_control), (10, 10_000, 40_000));
+        assert_eq!((args.max_volatility_accumulator, args.min_bin_id, args.protocol_share), (350_000, -1_000, 500));
+
+        let data = instruction_data("update_fee_parameters", (500u16, 8_000u16));
+        let Some(PairInstruction::UpdateFeeParameters(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!((args.protocol_share, args.base_factor), (500, 8_000));
+    }
+
+    #[test]
+    fn decodes_swap_event() {
+        let fields = ([1u8; 32], [2u8; 32], -120i32, -123i32, 2_000u64, 300u64, true, 5u64, 1u64, 25u128, 0u64);
+        let Some(PairEvent::Swap(event)) = decode_event(1, &event_data("Swap", fields)) else { panic!() };
+        assert_eq!((event.lb_pair, event.from), ([1; 32], [2; 32]));
+        assert_eq!((event.start_bin_id, event.end_bin_id, event.amount_in, event.amount_out), (-120, -123, 2_000, 300));
+        assert_eq!((event.swap_for_y, event.fee, event.protocol_fee, event.fee_bps), (true, 5, 1, 25));
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let data = instruction_data("swap", (1u64, 0u64));
+        assert!(decode_instruction(1, &data[..12]).is_none());
+        assert!(decode_event(1, &EVENT_IX_TAG).is_none());
+    }
+
+    #[test]
+    fn decodes_only_implemented_layouts() {
+        let data = instruction_data("swap", (1u64, 0u64));
+        assert!(decode_instruction(1, &data).is_some());
+        assert!(decode_instruction(0, &data).is_none());
+        assert!(decode_instruction(2, &data).is_none());
+    }
+
+    #[test]
+    fn splits_fee_into_base_and_variable() {
+        // base_factor 10_000 at bin step 10 is a 0.1% base fee
+        assert!((base_fee_rate(10_000, 10) - 0.001).abs() < 1e-12);
+        // variable_fee_control 40_000 with an accumulator of 100_000 at bin step 10: 40_000 * 1e12 / 1e11 / 1e9
+        let variable_fee_rate = 40_000.0 * (100_000.0f64 * 10.0).powi(2) / 100_000_000_000.0 / FEE_PRECISION;
+        assert_eq!(volatility_accumulator(variable_fee_rate, 40_000, 10), 100_000);
+        assert_eq!(volatility_accumulator(0.0, 40_000, 10), 0);
+    }
+}
@@ -0,0 +1,430 @@
+use borsh::BorshDeserialize;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const WITHDRAW2: [u8; 8] = [80, 6, 111, 73, 174, 211, 66, 132];
+const WITHDRAW_DIRECTLY_FROM_STRATEGY: [u8; 8] = [201, 141, 146, 46, 173, 116, 198, 22];
+const INITIALIZE_STRATEGY: [u8; 8] = [208, 119, 144, 145, 178, 57, 105, 252];
+const ADD_STRATEGY: [u8; 8] = [64, 123, 127, 227, 192, 234, 198, 20];
+const REMOVE_STRATEGY: [u8; 8] = [185, 238, 33, 91, 134, 210, 97, 26];
+const REMOVE_STRATEGY2: [u8; 8] = [138, 104, 208, 148, 126, 35, 195, 14];
+const DEPOSIT_STRATEGY: [u8; 8] = [246, 82, 57, 226, 131, 222, 253, 249];
+const WITHDRAW_STRATEGY: [u8; 8] = [31, 45, 162, 5, 193, 217, 134, 188];
+const COLLECT_DUST: [u8; 8] = [246, 149, 21, 82, 160, 74, 254, 240];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    INITIALIZE,
+    DEPOSIT,
+    WITHDRAW,
+    WITHDRAW2,
+    WITHDRAW_DIRECTLY_FROM_STRATEGY,
+    INITIALIZE_STRATEGY,
+    ADD_STRATEGY,
+    REMOVE_STRATEGY,
+    REMOVE_STRATEGY2,
+    DEPOSIT_STRATEGY,
+    WITHDRAW_STRATEGY,
+    COLLECT_DUST,
+];
+
+// IDL instructions the decoder leaves undecoded, so they are not reported as unknown discriminators
+pub const UNDECODED_INSTRUCTIONS: &[(&str, [u8; 8])] = &[
+    ("enable_vault", [145, 82, 241, 156, 26, 154, 233, 211]),
+    ("set_operator", [238, 153, 101, 169, 243, 131, 36, 1]),
+    ("update_locked_profit_degradation", [103, 192, 9, 190, 43, 209, 235, 115]),
+    ("get_unlocked_amount", [22, 184, 50, 213, 60, 168, 181, 227]),
+    ("transfer_admin", [42, 242, 66, 106, 228, 10, 111, 156]),
+    ("transfer_fee_vault", [24, 18, 129, 149, 149, 32, 45, 105]),
+    ("initialize_idle_vault", [100, 187, 43, 147, 149, 180, 117, 223]),
+];
+
+// Anchor event discriminators: sha256("event:<Name>")[..8]
+const ADD_LIQUIDITY_EVENT: [u8; 8] = [31, 94, 125, 90, 227, 52, 61, 186];
+const REMOVE_LIQUIDITY_EVENT: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
+const TOTAL_AMOUNT_EVENT: [u8; 8] = [92, 200, 122, 145, 211, 203, 49, 205];
+const PERFORMANCE_FEE_EVENT: [u8; 8] = [28, 70, 231, 223, 81, 109, 239, 167];
+const REPORT_LOSS_EVENT: [u8; 8] = [154, 36, 158, 196, 32, 163, 123, 126];
+
+// Event discriminators known to the decoder
+pub const EVENTS: &[[u8; 8]] = &[
+    ADD_LIQUIDITY_EVENT,
+    REMOVE_LIQUIDITY_EVENT,
+    TOTAL_AMOUNT_EVENT,
+    PERFORMANCE_FEE_EVENT,
+    REPORT_LOSS_EVENT,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const INITIALIZE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(2, "token_vault")];
+const USER_FLOW_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(1, "token_vault"), (3, "user_token"), (4, "user_lp")];
+const DIRECT_STRATEGY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (4, "collateral_vault"),
+    (5, "token_vault"),
+    (7, "fee_vault"),
+    (8, "user_token"),
+    (9, "user_lp"),
+];
+const INITIALIZE_STRATEGY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(4, "collateral_vault")];
+const REBALANCE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(2, "token_vault"), (3, "fee_vault"), (6, "collateral_vault")];
+const REMOVE_STRATEGY_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(3, "collateral_vault"), (5, "token_vault"), (6, "fee_vault")];
+const REMOVE_STRATEGY2_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (3, "collateral_vault"),
+    (5, "token_vault"),
+    (6, "token_admin_advance_payment"),
+    (7, "token_vault_advance_payment"),
+    (8, "fee_vault"),
+];
+const COLLECT_DUST_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(1, "token_vault"), (2, "token_admin")];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct DepositArgs {
+    pub token_amount: u64,
+    pub minimum_lp_token_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct WithdrawArgs {
+    pub unmint_amount: u64,
+    pub min_out_amount: u64,
+}
+
+// Lending protocol behind a strategy, in IDL declaration order
+#[derive(BorshDeserialize, Debug, Clone, Copy)]
+pub enum StrategyType {
+    PortFinanceWithoutLm,
+    PortFinanceWithLm,
+    SolendWithoutLm,
+    Mango,
+    SolendWithLm,
+    ApricotWithoutLm,
+    Francium,
+    Tulip,
+    Vault,
+    Drift,
+    Frakt,
+    Marginfi,
+    Kamino,
+}
+
+impl StrategyType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StrategyType::PortFinanceWithoutLm => "port_finance_without_lm",
+            StrategyType::PortFinanceWithLm => "port_finance_with_lm",
+            StrategyType::SolendWithoutLm => "solend_without_lm",
+            StrategyType::Mango => "mango",
+            StrategyType::SolendWithLm => "solend_with_lm",
+            StrategyType::ApricotWithoutLm => "apricot_without_lm",
+            StrategyType::Francium => "francium",
+            StrategyType::Tulip => "tulip",
+            StrategyType::Vault => "vault",
+            StrategyType::Drift => "drift",
+            StrategyType::Frakt => "frakt",
+            StrategyType::Marginfi => "marginfi",
+            StrategyType::Kamino => "kamino",
+        }
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct StrategyBumps {
+    pub strategy_index: u8,
+    pub other_bumps: [u8; 10],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializeStrategyArgs {
+    pub bumps: StrategyBumps,
+    pub strategy_type: StrategyType,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RebalanceArgs {
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RemoveStrategy2Args {
+    pub max_admin_pay_amount: u64,
+}
+
+#[derive(Debug)]
+pub enum VaultInstruction {
+    Initialize,
+    Deposit(DepositArgs),
+    Withdraw(WithdrawArgs),
+    Withdraw2(WithdrawArgs),
+    WithdrawDirectlyFromStrategy(WithdrawArgs),
+    InitializeStrategy(InitializeStrategyArgs),
+    AddStrategy,
+    RemoveStrategy,
+    RemoveStrategy2(RemoveStrategy2Args),
+    DepositStrategy(RebalanceArgs),
+    WithdrawStrategy(RebalanceArgs),
+    CollectDust,
+}
+
+impl VaultInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VaultInstruction::Initialize => "initialize",
+            VaultInstruction::Deposit(_) => "deposit",
+            VaultInstruction::Withdraw(_) => "withdraw",
+            VaultInstruction::Withdraw2(_) => "withdraw2",
+            VaultInstruction::WithdrawDirectlyFromStrategy(_) => "withdraw_directly_from_strategy",
+            VaultInstruction::InitializeStrategy(_) => "initialize_strategy",
+            VaultInstruction::AddStrategy => "add_strategy",
+            VaultInstruction::RemoveStrategy => "remove_strategy",
+            VaultInstruction::RemoveStrategy2(_) => "remove_strategy2",
+            VaultInstruction::DepositStrategy(_) => "deposit_strategy",
+            VaultInstruction::WithdrawStrategy(_) => "withdraw_strategy",
+            VaultInstruction::CollectDust => "collect_dust",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            VaultInstruction::Initialize => INITIALIZE_TOKEN_ACCOUNTS,
+            VaultInstruction::Deposit(_) | VaultInstruction::Withdraw(_) | VaultInstruction::Withdraw2(_) => {
+                USER_FLOW_TOKEN_ACCOUNTS
+            }
+            VaultInstruction::WithdrawDirectlyFromStrategy(_) => DIRECT_STRATEGY_TOKEN_ACCOUNTS,
+            VaultInstruction::InitializeStrategy(_) => INITIALIZE_STRATEGY_TOKEN_ACCOUNTS,
+            VaultInstruction::AddStrategy => &[],
+            VaultInstruction::RemoveStrategy => REMOVE_STRATEGY_TOKEN_ACCOUNTS,
+            VaultInstruction::RemoveStrategy2(_) => REMOVE_STRATEGY2_TOKEN_ACCOUNTS,
+            VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_) => REBALANCE_TOKEN_ACCOUNTS,
+            VaultInstruction::CollectDust => COLLECT_DUST_TOKEN_ACCOUNTS,
+        }
+    }
+
+    // Position of the signing authority in the instruction accounts (`payer`, `user`, `operator` or `admin`)
+    pub fn authority(&self) -> usize {
+        match self {
+            VaultInstruction::Initialize => 1,
+            VaultInstruction::Deposit(_) | VaultInstruction::Withdraw(_) | VaultInstruction::Withdraw2(_) => 5,
+            VaultInstruction::WithdrawDirectlyFromStrategy(_) => 10,
+            VaultInstruction::InitializeStrategy(_) => 6,
+            VaultInstruction::AddStrategy => 2,
+            VaultInstruction::RemoveStrategy => 9,
+            VaultInstruction::RemoveStrategy2(_) => 11,
+            VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_) => 9,
+            VaultInstruction::CollectDust => 3,
+        }
+    }
+
+    // Position of the vault reserve `token_vault` account
+    pub fn token_vault(&self) -> usize {
+        match self {
+            VaultInstruction::Deposit(_)
+            | VaultInstruction::Withdraw(_)
+            | VaultInstruction::Withdraw2(_)
+            | VaultInstruction::CollectDust => 1,
+            VaultInstruction::Initialize | VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_) => 2,
+            VaultInstruction::WithdrawDirectlyFromStrategy(_)
+            | VaultInstruction::RemoveStrategy
+            | VaultInstruction::RemoveStrategy2(_) => 5,
+            // initialize_strategy and add_strategy do not reference the reserve
+            VaultInstruction::InitializeStrategy(_) | VaultInstruction::AddStrategy => 0,
+        }
+    }
+
+    // Position of the `fee_vault` token account receiving performance fee LP tokens
+    pub fn fee_vault(&self) -> Option<usize> {
+        match self {
+            VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_) => Some(3),
+            VaultInstruction::RemoveStrategy => Some(6),
+            VaultInstruction::WithdrawDirectlyFromStrategy(_) => Some(7),
+            VaultInstruction::RemoveStrategy2(_) => Some(8),
+            _ => None,
+        }
+    }
+
+    // Position of the strategy account for instructions acting on a single strategy
+    pub fn strategy(&self) -> Option<usize> {
+        match self {
+            VaultInstruction::WithdrawDirectlyFromStrategy(_)
+            | VaultInstruction::AddStrategy
+            | VaultInstruction::RemoveStrategy
+            | VaultInstruction::RemoveStrategy2(_)
+            | VaultInstruction::DepositStrategy(_)
+            | VaultInstruction::WithdrawStrategy(_) => Some(1),
+            VaultInstruction::InitializeStrategy(_) => Some(2),
+            _ => None,
+        }
+    }
+}
+
+// Decode a Dynamic Vault instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<VaultInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Dynamic Vault instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<VaultInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == INITIALIZE => Some(VaultInstruction::Initialize),
+        d if d == DEPOSIT => DepositArgs::deserialize(&mut args).ok().map(VaultInstruction::Deposit),
+        d if d == WITHDRAW => WithdrawArgs::deserialize(&mut args).ok().map(VaultInstruction::Withdraw),
+        d if d == WITHDRAW2 => WithdrawArgs::deserialize(&mut args).ok().map(VaultInstruction::Withdraw2),
+        d if d == WITHDRAW_DIRECTLY_FROM_STRATEGY => WithdrawArgs::deserialize(&mut args)
+            .ok()
+            .map(VaultInstruction::WithdrawDirectlyFromStrategy),
+        d if d == INITIALIZE_STRATEGY => InitializeStrategyArgs::deserialize(&mut args)
+            .ok()
+            .map(VaultInstruction::InitializeStrategy),
+        d if d == ADD_STRATEGY => Some(VaultInstruction::AddStrategy),
+        d if d == REMOVE_STRATEGY => Some(VaultInstruction::RemoveStrategy),
+        d if d == REMOVE_STRATEGY2 => RemoveStrategy2Args::deserialize(&mut args)
+            .ok()
+            .map(VaultInstruction::RemoveStrategy2),
+        d if d == DEPOSIT_STRATEGY => RebalanceArgs::deserialize(&mut args).ok().map(VaultInstruction::DepositStrategy),
+        d if d == WITHDRAW_STRATEGY => RebalanceArgs::deserialize(&mut args).ok().map(VaultInstruction::WithdrawStrategy),
+        d if d == COLLECT_DUST => Some(VaultInstruction::CollectDust),
+        _ => None,
+    }
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct AddLiquidityEvent {
+    pub lp_mint_amount: u64,
+    pub token_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct RemoveLiquidityEvent {
+    pub lp_unmint_amount: u64,
+    pub token_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct TotalAmountEvent {
+    pub total_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct PerformanceFeeEvent {
+    pub lp_mint_more: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct ReportLossEvent {
+    pub strategy: [u8; 32],
+    pub loss: u64,
+}
+
+#[derive(Debug)]
+pub enum VaultEvent {
+    AddLiquidity(AddLiquidityEvent),
+    RemoveLiquidity(RemoveLiquidityEvent),
+    TotalAmount(TotalAmountEvent),
+    PerformanceFee(PerformanceFeeEvent),
+    ReportLoss(ReportLossEvent),
+}
+
+// Decode a Dynamic Vault event with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_event(idl_version: u32, data: &[u8]) -> Option<VaultEvent> {
+    match idl_version {
+        1 => decode_event_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Dynamic Vault Anchor event from a "Program data:" log payload
+fn decode_event_v1(data: &[u8]) -> Option<VaultEvent> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut fields) = data.split_at(8);
+
+    match discriminator {
+        d if d == ADD_LIQUIDITY_EVENT => AddLiquidityEvent::deserialize(&mut fields).ok().map(VaultEvent::AddLiquidity),
+        d if d == REMOVE_LIQUIDITY_EVENT => RemoveLiquidityEvent::deserialize(&mut fields)
+            .ok()
+            .map(VaultEvent::RemoveLiquidity),
+        d if d == TOTAL_AMOUNT_EVENT => TotalAmountEvent::deserialize(&mut fields).ok().map(VaultEvent::TotalAmount),
+        d if d == PERFORMANCE_FEE_EVENT => PerformanceFeeEvent::deserialize(&mut fields)
+            .ok()
+            .map(VaultEvent::PerformanceFee),
+        d if d == REPORT_LOSS_EVENT => ReportLossEvent::deserialize(&mut fields).ok().map(VaultEvent::ReportLoss),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator, event_payload, instruction_data};
+
+    #[test]
+    fn undecoded_instructions_are_idl_instructions() {
+        for (name, expected) in UNDECODED_INSTRUCTIONS {
+            assert_eq!(&discriminator("global", name), expected, "{}", name);
+            assert!(!INSTRUCTIONS.contains(expected), "{} is decoded", name);
+        }
+    }
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, VaultInstruction::name);
+        for (expected, name) in [
+            (ADD_LIQUIDITY_EVENT, "AddLiquidity"),
+            (REMOVE_LIQUIDITY_EVENT, "RemoveLiquidity"),
+            (TOTAL_AMOUNT_EVENT, "TotalAmount"),
+            (PERFORMANCE_FEE_EVENT, "PerformanceFee"),
+            (REPORT_LOSS_EVENT, "ReportLoss"),
+        ] {
+            assert_eq!(discriminator("event", name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn decodes_user_flow_args() {
+        let Some(VaultInstruction::Deposit(args)) = decode_instruction(1, &instruction_data("deposit", (100u64, 95u64)))
+        else {
+            panic!()
+        };
+        assert_eq!((args.token_amount, args.minimum_lp_token_amount), (100, 95));
+
+        let Some(VaultInstruction::Withdraw2(args)) = decode_instruction(1, &instruction_data("withdraw2", (50u64, 49u64)))
+        else {
+            panic!()
+        };
+        assert_eq!((args.unmint_amount, args.min_out_amount), (50, 49));
+    }
+
+    #[test]
+    fn decodes_strategy_type_in_idl_order() {
+        // StrategyBumps, then the StrategyType variant index
+        let data = instruction_data("initialize_strategy", (3u8, [0u8; 10], 12u8));
+        let Some(VaultInstruction::InitializeStrategy(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!(args.bumps.strategy_index, 3);
+        assert_eq!(args.strategy_type.name(), "kamino");
+
+        assert!(decode_instruction(1, &instruction_data("initialize_strategy", (3u8, [0u8; 10], 13u8))).is_none());
+    }
+
+    #[test]
+    fn decodes_logged_events() {
+        let Some(VaultEvent::TotalAmount(event)) = decode_event(1, &event_payload("TotalAmount", 1_000_000u64)) else {
+            panic!()
+        };
+        assert_eq!(event.total_amount, 1_000_000);
+
+        let Some(VaultEvent::ReportLoss(event)) = decode_event(1, &event_payload("ReportLoss", ([7u8; 32], 42u64))) else {
+            panic!()
+        };
+        assert_eq!((event.strategy, event.loss), ([7; 32], 42));
+    }
+}
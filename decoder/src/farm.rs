@@ -0,0 +1,259 @@
+use borsh::BorshDeserialize;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const INITIALIZE_POOL: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+const CREATE_USER: [u8; 8] = [108, 227, 130, 130, 252, 109, 75, 218];
+const PAUSE: [u8; 8] = [211, 22, 221, 251, 74, 121, 193, 47];
+const UNPAUSE: [u8; 8] = [169, 144, 4, 38, 10, 141, 188, 255];
+const DEPOSIT: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];
+const WITHDRAW: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+const AUTHORIZE_FUNDER: [u8; 8] = [18, 74, 66, 11, 50, 8, 89, 213];
+const DEAUTHORIZE_FUNDER: [u8; 8] = [75, 130, 38, 206, 85, 75, 82, 37];
+const FUND: [u8; 8] = [218, 188, 111, 221, 152, 113, 174, 7];
+const CLAIM: [u8; 8] = [62, 198, 214, 193, 213, 159, 108, 210];
+const CLOSE_USER: [u8; 8] = [86, 219, 138, 140, 236, 24, 118, 200];
+const UPDATE_REWARD_DURATION: [u8; 8] = [138, 174, 196, 169, 213, 235, 254, 107];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    INITIALIZE_POOL,
+    CREATE_USER,
+    PAUSE,
+    UNPAUSE,
+    DEPOSIT,
+    WITHDRAW,
+    AUTHORIZE_FUNDER,
+    DEAUTHORIZE_FUNDER,
+    FUND,
+    CLAIM,
+    CLOSE_USER,
+    UPDATE_REWARD_DURATION,
+];
+
+// Token accounts per instruction, as (account position, IDL name)
+const INITIALIZE_POOL_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(2, "staking_vault"), (4, "reward_a_vault"), (6, "reward_b_vault")];
+const STAKE_TOKEN_ACCOUNTS: &[(usize, &str)] = &[(1, "staking_vault"), (4, "stake_from_account")];
+const FUND_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (1, "staking_vault"),
+    (2, "reward_a_vault"),
+    (3, "reward_b_vault"),
+    (5, "from_a"),
+    (6, "from_b"),
+];
+const CLAIM_TOKEN_ACCOUNTS: &[(usize, &str)] = &[
+    (1, "staking_vault"),
+    (2, "reward_a_vault"),
+    (3, "reward_b_vault"),
+    (6, "reward_a_account"),
+    (7, "reward_b_account"),
+];
+
+#[derive(BorshDeserialize, Debug)]
+pub struct InitializePoolArgs {
+    pub reward_duration: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct DepositArgs {
+    pub amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct WithdrawArgs {
+    pub spt_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct FunderArgs {
+    pub funder: [u8; 32],
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct FundArgs {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[derive(BorshDeserialize, Debug)]
+pub struct UpdateRewardDurationArgs {
+    pub new_reward_duration: u64,
+}
+
+#[derive(Debug)]
+pub enum FarmInstruction {
+    InitializePool(InitializePoolArgs),
+    CreateUser,
+    Pause,
+    Unpause,
+    Deposit(DepositArgs),
+    Withdraw(WithdrawArgs),
+    AuthorizeFunder(FunderArgs),
+    DeauthorizeFunder(FunderArgs),
+    Fund(FundArgs),
+    Claim,
+    CloseUser,
+    UpdateRewardDuration(UpdateRewardDurationArgs),
+}
+
+impl FarmInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FarmInstruction::InitializePool(_) => "initialize_pool",
+            FarmInstruction::CreateUser => "create_user",
+            FarmInstruction::Pause => "pause",
+            FarmInstruction::Unpause => "unpause",
+            FarmInstruction::Deposit(_) => "deposit",
+            FarmInstruction::Withdraw(_) => "withdraw",
+            FarmInstruction::AuthorizeFunder(_) => "authorize_funder",
+            FarmInstruction::DeauthorizeFunder(_) => "deauthorize_funder",
+            FarmInstruction::Fund(_) => "fund",
+            FarmInstruction::Claim => "claim",
+            FarmInstruction::CloseUser => "close_user",
+            FarmInstruction::UpdateRewardDuration(_) => "update_reward_duration",
+        }
+    }
+
+    pub fn token_accounts(&self) -> &'static [(usize, &'static str)] {
+        match self {
+            FarmInstruction::InitializePool(_) => INITIALIZE_POOL_TOKEN_ACCOUNTS,
+            FarmInstruction::Deposit(_) | FarmInstruction::Withdraw(_) => STAKE_TOKEN_ACCOUNTS,
+            FarmInstruction::Fund(_) => FUND_TOKEN_ACCOUNTS,
+            FarmInstruction::Claim => CLAIM_TOKEN_ACCOUNTS,
+            _ => &[],
+        }
+    }
+
+    // Position of the signing `authority`, `owner` or `funder` account in the instruction accounts
+    pub fn authority(&self) -> usize {
+        match self {
+            FarmInstruction::InitializePool(_) => 7,
+            FarmInstruction::CreateUser | FarmInstruction::CloseUser => 2,
+            FarmInstruction::Deposit(_) | FarmInstruction::Withdraw(_) => 3,
+            FarmInstruction::Fund(_) => 4,
+            FarmInstruction::Claim => 5,
+            FarmInstruction::Pause
+            | FarmInstruction::Unpause
+            | FarmInstruction::AuthorizeFunder(_)
+            | FarmInstruction::DeauthorizeFunder(_)
+            | FarmInstruction::UpdateRewardDuration(_) => 1,
+        }
+    }
+
+    // Position of the per-wallet `user` stake account, for instructions acting on a staking position
+    pub fn user(&self) -> Option<usize> {
+        match self {
+            FarmInstruction::CreateUser | FarmInstruction::CloseUser => Some(1),
+            FarmInstruction::Deposit(_) | FarmInstruction::Withdraw(_) => Some(2),
+            FarmInstruction::Claim => Some(4),
+            _ => None,
+        }
+    }
+
+    // Signed raw amount the instruction moves into the farm `staking_vault`
+    pub fn staked_change(&self) -> i128 {
+        match self {
+            FarmInstruction::Deposit(args) => args.amount as i128,
+            FarmInstruction::Withdraw(args) => -(args.spt_amount as i128),
+            _ => 0,
+        }
+    }
+
+    // Positions of the owner `reward_a_account` and `reward_b_account` token accounts rewards are paid to
+    pub fn reward_accounts(&self) -> Option<(usize, usize)> {
+        match self {
+            FarmInstruction::Claim => Some((6, 7)),
+            _ => None,
+        }
+    }
+
+    // Position of the farm `staking_vault` token account
+    pub fn staking_vault(&self) -> Option<usize> {
+        match self {
+            FarmInstruction::InitializePool(_) => Some(2),
+            FarmInstruction::Deposit(_) | FarmInstruction::Withdraw(_) | FarmInstruction::Fund(_) | FarmInstruction::Claim => {
+                Some(1)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Decode a Farm instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<FarmInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Farm instruction from its Anchor discriminator and Borsh arguments
+fn decode_instruction_v1(data: &[u8]) -> Option<FarmInstruction> {
+    if data.len() < 8 {
+        return None;
+    }
+    let (discriminator, mut args) = data.split_at(8);
+
+    match discriminator {
+        d if d == INITIALIZE_POOL => InitializePoolArgs::deserialize(&mut args)
+            .ok()
+            .map(FarmInstruction::InitializePool),
+        d if d == CREATE_USER => Some(FarmInstruction::CreateUser),
+        d if d == PAUSE => Some(FarmInstruction::Pause),
+        d if d == UNPAUSE => Some(FarmInstruction::Unpause),
+        d if d == DEPOSIT => DepositArgs::deserialize(&mut args).ok().map(FarmInstruction::Deposit),
+        d if d == WITHDRAW => WithdrawArgs::deserialize(&mut args).ok().map(FarmInstruction::Withdraw),
+        d if d == AUTHORIZE_FUNDER => FunderArgs::deserialize(&mut args)
+            .ok()
+            .map(FarmInstruction::AuthorizeFunder),
+        d if d == DEAUTHORIZE_FUNDER => FunderArgs::deserialize(&mut args)
+            .ok()
+            .map(FarmInstruction::DeauthorizeFunder),
+        d if d == FUND => FundArgs::deserialize(&mut args).ok().map(FarmInstruction::Fund),
+        d if d == CLAIM => Some(FarmInstruction::Claim),
+        d if d == CLOSE_USER => Some(FarmInstruction::CloseUser),
+        d if d == UPDATE_REWARD_DURATION => UpdateRewardDurationArgs::deserialize(&mut args)
+            .ok()
+            .map(FarmInstruction::UpdateRewardDuration),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, instruction_data};
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, FarmInstruction::name);
+    }
+
+    #[test]
+    fn decodes_stake_changes() {
+        let deposit = decode_instruction(1, &instruction_data("deposit", 1_500u64)).unwrap();
+        let withdraw = decode_instruction(1, &instruction_data("withdraw", 400u64)).unwrap();
+        assert_eq!((deposit.staked_change(), withdraw.staked_change()), (1_500, -400));
+        assert_eq!((deposit.user(), deposit.staking_vault()), (Some(2), Some(1)));
+    }
+
+    #[test]
+    fn decodes_funding_args() {
+        let Some(FarmInstruction::Fund(args)) = decode_instruction(1, &instruction_data("fund", (10u64, 20u64))) else {
+            panic!()
+        };
+        assert_eq!((args.amount_a, args.amount_b), (10, 20));
+
+        let data = instruction_data("authorize_funder", [9u8; 32]);
+        let Some(FarmInstruction::AuthorizeFunder(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!(args.funder, [9; 32]);
+
+        let data = instruction_data("update_reward_duration", 86_400u64);
+        let Some(FarmInstruction::UpdateRewardDuration(args)) = decode_instruction(1, &data) else { panic!() };
+        assert_eq!(args.new_reward_duration, 86_400);
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        assert!(decode_instruction(1, &instruction_data("fund", 10u64)).is_none());
+    }
+}
@@ -0,0 +1,1895 @@
+pub mod damm_v1;
+pub mod damm_v2;
+pub mod dbc;
+pub mod dlmm;
+pub mod dynamic_vault;
+pub mod farm;
+mod logs;
+pub mod pb;
+mod signers;
+#[cfg(test)]
+mod test_utils;
+pub mod token_accounts;
+pub mod versions;
+pub mod zap;
+
+use dlmm::{PairEvent, PairInstruction};
+use dynamic_vault::{VaultEvent, VaultInstruction};
+use farm::FarmInstruction;
+use logs::InvocationLogs;
+use pb::meteora::v1::{
+    DlmmBinLiquidity, DlmmPairCreated, DlmmPositionEvent, DlmmPositionEvents,
+    DlmmFeeParameterEvent, DlmmFeeParameterEvents, DlmmSwap, DlmmSwapEvents,
+    FarmEmissionEvent, FarmEmissionEvents,
+    DecodeError, DecodeErrors, FarmPositionEvent, FarmPositionEvents, MeteoraEvent, MeteoraInstruction, MeteoraInstructions, MeteoraTransaction, PoolCreated, PoolFeeRate, PoolFeeRates, PoolsCreated, PoolSwap, PoolSwaps, VaultFeeEvent, VaultFeeEvents, VaultSharePrice,
+    VaultFlow, VaultFlows, VaultSharePrices, VaultStrategyEvent, VaultStrategyEvents, VaultTotalAmount, ZapEvent,
+    ZapEvents, ZapLiquidity, ZapSwap,
+};
+use std::collections::HashMap;
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams_solana::b58;
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+use token_accounts::PendingTokenAccount;
+
+// Program IDs for Meteora contracts
+pub const DYNAMIC_VAULT_PROGRAM: &str = "24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi";
+pub const FARM_PROGRAM: &str = "FarmuwXPWXvefWUeqFAa5w6rifLkq5X6E8bimYvrhCB1";
+pub const ZAP_PROGRAM: &str = "zapvX9M3uf5pvy4wRPAbQgdQsM1xmuiFnkfHKPvwMiz";
+pub const DAMM_V1_PROGRAM: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB"; // Added DAMM v1
+pub const DLMM_PROGRAM: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+pub const DAMM_V2_PROGRAM: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+pub const DBC_PROGRAM: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+
+// Same program IDs decoded at compile time, compared against raw account keys without base58 encoding
+pub const DYNAMIC_VAULT_PROGRAM_ID: [u8; 32] = b58!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi");
+pub const FARM_PROGRAM_ID: [u8; 32] = b58!("FarmuwXPWXvefWUeqFAa5w6rifLkq5X6E8bimYvrhCB1");
+pub const ZAP_PROGRAM_ID: [u8; 32] = b58!("zapvX9M3uf5pvy4wRPAbQgdQsM1xmuiFnkfHKPvwMiz");
+pub const DAMM_V1_PROGRAM_ID: [u8; 32] = b58!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB");
+pub const DLMM_PROGRAM_ID: [u8; 32] = b58!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+pub const DAMM_V2_PROGRAM_ID: [u8; 32] = b58!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+pub const DBC_PROGRAM_ID: [u8; 32] = b58!("dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN");
+
+// Anchor `emit_cpi!` self-invocation tag: sha256("anchor:event")[..8], little endian
+pub(crate) const EVENT_IX_TAG: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
+
+// Typed view of an instruction from a program with an IDL decoder
+struct InstructionLayout {
+    name: &'static str,
+    token_accounts: &'static [(usize, &'static str)],
+    authority: Option<usize>,
+}
+
+// Decoded instruction name, token account layout and authority position for programs with typed decoders
+fn decode_instruction_layout(program_id: &str, idl_version: u32, instruction_data: &[u8]) -> Option<InstructionLayout> {
+    match program_id {
+        DYNAMIC_VAULT_PROGRAM => dynamic_vault::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        FARM_PROGRAM => farm::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        // Zap instructions hand the user accounts on to the DEX they call, so their events keep the fee payer as signer
+        ZAP_PROGRAM => zap::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: &[],
+            authority: None,
+        }),
+        DAMM_V1_PROGRAM => damm_v1::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        DLMM_PROGRAM => dlmm::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        DAMM_V2_PROGRAM => damm_v2::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        DBC_PROGRAM => dbc::decode_instruction(idl_version, instruction_data).map(|inst| InstructionLayout {
+            name: inst.name(),
+            token_accounts: inst.token_accounts(),
+            authority: Some(inst.authority()),
+        }),
+        _ => None,
+    }
+}
+
+// Programs whose instructions are kept by `meteora_instructions`, with the base58 ID they are emitted with
+const METEORA_PROGRAMS: [([u8; 32], &str); 7] = [
+    (DYNAMIC_VAULT_PROGRAM_ID, DYNAMIC_VAULT_PROGRAM),
+    (FARM_PROGRAM_ID, FARM_PROGRAM),
+    (ZAP_PROGRAM_ID, ZAP_PROGRAM),
+    (DAMM_V1_PROGRAM_ID, DAMM_V1_PROGRAM),
+    (DLMM_PROGRAM_ID, DLMM_PROGRAM),
+    (DAMM_V2_PROGRAM_ID, DAMM_V2_PROGRAM),
+    (DBC_PROGRAM_ID, DBC_PROGRAM),
+];
+
+// Base58 ID of the Meteora program with this raw key
+fn meteora_program(key: &[u8]) -> Option<&'static str> {
+    METEORA_PROGRAMS
+        .iter()
+        .find(|(id, _)| id.as_slice() == key)
+        .map(|(_, program_id)| *program_id)
+}
+
+// Raw key of the Meteora program with this base58 ID
+fn meteora_program_key(program_id: &str) -> Option<&'static [u8; 32]> {
+    METEORA_PROGRAMS.iter().find(|(_, id)| *id == program_id).map(|(key, _)| key)
+}
+
+// Base58 form of the account keys of a transaction, each encoded once however many instructions reference it
+#[derive(Default)]
+struct EncodedKeys(HashMap<Vec<u8>, String>);
+
+impl EncodedKeys {
+    fn get(&mut self, key: &[u8]) -> String {
+        if let Some(encoded) = self.0.get(key) {
+            return encoded.clone();
+        }
+        let encoded = bs58::encode(key).into_string();
+        self.0.insert(key.to_vec(), encoded.clone());
+        encoded
+    }
+}
+
+// Instruction name from the decoder of the program layout, None for undecoded instructions
+fn instruction_name(program_id: &str, idl_version: u32, data: &[u8]) -> Option<&'static str> {
+    match program_id {
+        DYNAMIC_VAULT_PROGRAM => dynamic_vault::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        FARM_PROGRAM => farm::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        ZAP_PROGRAM => zap::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        DAMM_V1_PROGRAM => damm_v1::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        DLMM_PROGRAM => dlmm::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        DAMM_V2_PROGRAM => damm_v2::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        DBC_PROGRAM => dbc::decode_instruction(idl_version, data).map(|inst| inst.name()),
+        zap::JUPITER_PROGRAM => zap::jupiter_route_name(data),
+        token_accounts::TOKEN_PROGRAM | token_accounts::TOKEN_2022_PROGRAM => token_accounts::transfer_name(data),
+        _ => None,
+    }
+}
+
+// Single pass over the block: every transaction invoking a Meteora program, with its Meteora instructions (and the
+// Jupiter routes and token transfers of zaps) in execution order, their accounts and logged event payloads, and the
+// token balances downstream modules read mints, decimals and amounts from
+pub fn meteora_instructions(block: &Block) -> MeteoraInstructions {
+    let mut transactions = Vec::new();
+
+    for trx in block.transactions() {
+        let Some(meta) = trx.meta.as_ref() else { continue };
+        // Logs are only parsed, and keys only encoded, for transactions invoking a Meteora program
+        let mut logs: Option<InvocationLogs> = None;
+        let mut keys = EncodedKeys::default();
+        let mut instructions = Vec::new();
+        // Jupiter routes are kept once a zap ran in the transaction, since zaps swap through them, either by CPI
+        // or as top-level instructions between `set_token_ledger` and the zap
+        let mut zap_seen = false;
+
+        for (instruction_index, view) in trx.compiled_instructions().enumerate() {
+            let mut outer_program: Option<String> = None;
+            let walk = std::iter::once(view.compiled_instruction()).chain(view.inner_instructions());
+            // Token transfers are kept below zaps and the Jupiter routes they use, for the amounts they swapped
+            let mut keep_transfers = false;
+
+            for (position, inst) in walk.enumerate() {
+                let key = inst.program_id();
+                let meteora = meteora_program(key.0);
+                zap_seen |= key == ZAP_PROGRAM_ID;
+                let program_id = match meteora {
+                    Some(program_id) => program_id,
+                    None if zap_seen && key == zap::JUPITER_PROGRAM_ID => zap::JUPITER_PROGRAM,
+                    None => match token_accounts::transfer_program(key.0, inst.data()) {
+                        Some(program_id) if keep_transfers => program_id,
+                        _ => continue,
+                    },
+                };
+                keep_transfers |= program_id == ZAP_PROGRAM || program_id == zap::JUPITER_PROGRAM;
+
+                // Every Meteora invocation claims its logs, in execution order
+                let log_events = match meteora {
+                    Some(program_id) => logs
+                        .get_or_insert_with(|| InvocationLogs::parse(&meta.log_messages))
+                        .next_for(program_id)
+                        .to_vec(),
+                    None => Vec::new(),
+                };
+                // Instructions from slots no implemented layout covers keep version 0, which no decoder parses,
+                // and are reported by `decode_errors`
+                let idl_version = meteora.and_then(|program_id| versions::idl_version(program_id, block.slot)).unwrap_or(0);
+                instructions.push(MeteoraInstruction {
+                    instruction_index: instruction_index as u32,
+                    inner_instruction_index: position.saturating_sub(1) as u32,
+                    is_inner_instruction: !inst.is_root(),
+                    stack_height: inst.stack_height(),
+                    instruction_type: instruction_name(program_id, idl_version, inst.data())
+                        .unwrap_or_default()
+                        .to_string(),
+                    idl_version,
+                    program_id: program_id.to_string(),
+                    outer_program: outer_program.get_or_insert_with(|| keys.get(view.program_id().0)).clone(),
+                    accounts: inst.accounts().into_iter().map(|account| keys.get(account.0)).collect(),
+                    data: inst.data().clone(),
+                    log_events,
+                });
+            }
+        }
+
+        if instructions.is_empty() {
+            continue;
+        }
+
+        let transaction_signers = signers::transaction_signers(trx);
+        transactions.push(MeteoraTransaction {
+            tx_id: trx.id(),
+            fee_payer: transaction_signers.fee_payer,
+            signers: transaction_signers.signers,
+            fee: meta.fee,
+            // The fee payer is always account 0, so balance index 0 is its SOL change
+            signer_sol_change: meta.post_balances.first().copied().unwrap_or_default() as i64
+                - meta.pre_balances.first().copied().unwrap_or_default() as i64,
+            token_balances: token_accounts::transaction_balances(trx),
+            instructions,
+        });
+    }
+
+    MeteoraInstructions {
+        block_slot: block.slot,
+        block_time: block.block_time.as_ref().map(|t| t.timestamp).unwrap_or_default(),
+        transactions,
+    }
+}
+
+// Account at a position of an instruction, empty when the instruction has fewer accounts
+fn account_at(accounts: &[String], position: usize) -> String {
+    accounts.get(position).cloned().unwrap_or_default()
+}
+
+// Instructions of a transaction grouped per compiled instruction, each group in execution order
+fn compiled_walks(trx: &MeteoraTransaction) -> impl Iterator<Item = &[MeteoraInstruction]> {
+    trx.instructions.chunk_by(|a, b| a.instruction_index == b.instruction_index)
+}
+
+// Build the event for a Meteora instruction, along with the token accounts awaiting owner resolution
+fn decode_meteora_event(inst: &MeteoraInstruction) -> Option<(MeteoraEvent, Vec<PendingTokenAccount>)> {
+    // Instructions matching no discriminator of their program are left out, and reported by `decode_errors`
+    let layout = decode_instruction_layout(&inst.program_id, inst.idl_version, &inst.data)?;
+    let mut pending = token_accounts::collect(&inst.accounts, layout.token_accounts);
+    // Anchor passes the program ID for optional accounts that are not provided
+    if let Some(program_key) = meteora_program_key(&inst.program_id) {
+        pending.retain(|account| account.address != *program_key);
+    }
+
+    let event = MeteoraEvent {
+        program_id: inst.program_id.clone(),
+        instruction_type: layout.name.to_string(),
+        accounts: inst.accounts.join(","),
+        instruction_data: inst.data.clone(),
+        is_inner_instruction: inst.is_inner_instruction,
+        authority: layout.authority.map(|position| account_at(&inst.accounts, position)).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    Some((event, pending))
+}
+
+// Every Meteora instruction of the block as an event, with block, transaction and signer context filled in,
+// along with the token accounts each one references that still need their owner resolved
+pub fn meteora_events(block: &MeteoraInstructions) -> Vec<(MeteoraEvent, Vec<PendingTokenAccount>)> {
+    let mut events = Vec::new();
+    let block_date = chrono::DateTime::from_timestamp(block.block_time, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+
+    for trx in &block.transactions {
+        for inst in &trx.instructions {
+            let Some((mut event, pending)) = decode_meteora_event(inst) else { continue };
+
+            event.block_date = block_date.clone();
+            event.block_time = block.block_time;
+            event.block_slot = block.block_slot;
+            event.tx_id = trx.tx_id.clone();
+            event.signer = if trx.signers.contains(&event.authority) {
+                event.authority.clone()
+            } else {
+                trx.fee_payer.clone()
+            };
+            event.fee_payer = trx.fee_payer.clone();
+            event.signers = trx.signers.clone();
+            event.instruction_index = inst.instruction_index;
+            event.inner_instruction_index = inst.inner_instruction_index;
+            event.outer_program = inst.outer_program.clone();
+            if event.is_inner_instruction {
+                event.inner_program = event.program_id.clone();
+            }
+            event.txn_fee = trx.fee;
+            event.signer_sol_change = trx.signer_sol_change;
+            events.push((event, pending));
+        }
+    }
+
+    events
+}
+
+// Row ID of an event: `{tx_id}-{instruction_index}` for top-level instructions and
+// `{tx_id}-{instruction_index}-{inner_instruction_index}` for CPIs, since both count their index from 0
+pub fn instruction_id(event: &MeteoraEvent) -> String {
+    if event.is_inner_instruction {
+        format!("{}-{}-{}", event.tx_id, event.instruction_index, event.inner_instruction_index)
+    } else {
+        format!("{}-{}", event.tx_id, event.instruction_index)
+    }
+}
+
+// Vault analytics module: signed per-vault, per-mint capital flows from deposits, withdrawals and strategy profit
+pub fn vault_analytics(block: &MeteoraInstructions, vault_fees: VaultFeeEvents) -> Result<VaultFlows, substreams::errors::Error> {
+    let mut flows: Vec<VaultFlow> = Vec::new();
+    let mut totals: Vec<(BigInt, BigInt, BigInt)> = Vec::new();
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    let flow_index = |flows: &mut Vec<VaultFlow>, totals: &mut Vec<(BigInt, BigInt, BigInt)>, vault: &str| {
+        if let Some(index) = flows.iter().position(|flow| flow.vault == vault) {
+            return index;
+        }
+        flows.push(VaultFlow {
+            vault: vault.to_string(),
+            block_slot: slot,
+            block_time,
+            ..Default::default()
+        });
+        totals.push((BigInt::zero(), BigInt::zero(), BigInt::zero()));
+        flows.len() - 1
+    };
+
+    for trx in &block.transactions {
+
+        for instructions in compiled_walks(trx) {
+            for inst in instructions {
+                // Focus only on Dynamic Vault Program
+                if inst.program_id != DYNAMIC_VAULT_PROGRAM {
+                    continue;
+                }
+                let payloads = &inst.log_events;
+                let Some(instruction) = dynamic_vault::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                let accounts = &inst.accounts;
+                let Some(vault) = accounts.first().cloned() else { continue };
+
+                let index = flow_index(&mut flows, &mut totals, &vault);
+                if flows[index].mint.is_empty() {
+                    if let Some(token_vault) = accounts.get(instruction.token_vault()) {
+                        flows[index].mint = token_accounts::mint_from_balances(trx, token_vault).unwrap_or_default();
+                    }
+                }
+
+                match instruction {
+                    VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_) => {
+                        flows[index].rebalance_count += 1;
+                    }
+                    VaultInstruction::Initialize => {
+                        substreams::log::info!("New vault {} initialized in slot {}", vault, slot);
+                    }
+                    _ => {}
+                }
+
+                for event in payloads.iter().filter_map(|payload| dynamic_vault::decode_event(inst.idl_version, payload)) {
+                    match event {
+                        VaultEvent::AddLiquidity(e) => {
+                            totals[index].0 = totals[index].0.clone() + BigInt::from(e.token_amount);
+                            flows[index].deposit_count += 1;
+                        }
+                        VaultEvent::RemoveLiquidity(e) => {
+                            totals[index].1 = totals[index].1.clone() + BigInt::from(e.token_amount);
+                            flows[index].withdrawal_count += 1;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    for fee in vault_fees.fees {
+        let index = flow_index(&mut flows, &mut totals, &fee.vault);
+        totals[index].2 = totals[index].2.clone() + BigInt::from(fee.profit);
+    }
+
+    for (flow, (deposits, withdrawals, strategy_profit)) in flows.iter_mut().zip(totals) {
+        let net_flow = deposits.clone() - withdrawals.clone() + strategy_profit.clone();
+        flow.deposits = deposits.to_string();
+        flow.withdrawals = withdrawals.to_string();
+        flow.strategy_profit = strategy_profit.to_string();
+        flow.net_flow = net_flow.to_string();
+
+        substreams::log::info!(
+            "Vault analytics for slot {}: vault {} ({}), {} deposits, {} withdrawals, {} strategy profit, net flow: {}, {} rebalances",
+            slot, flow.vault, flow.mint, flow.deposits, flow.withdrawals, flow.strategy_profit, flow.net_flow, flow.rebalance_count
+        );
+    }
+
+    Ok(VaultFlows { flows })
+}
+
+// Decode a Dynamic Vault strategy instruction into a strategy event
+fn decode_strategy_event(inst: &MeteoraInstruction) -> Option<VaultStrategyEvent> {
+    if inst.program_id != DYNAMIC_VAULT_PROGRAM {
+        return None;
+    }
+
+    let instruction = dynamic_vault::decode_instruction(inst.idl_version, &inst.data)?;
+    let (amount, strategy_type) = match &instruction {
+        VaultInstruction::DepositStrategy(args) | VaultInstruction::WithdrawStrategy(args) => (args.amount, ""),
+        VaultInstruction::InitializeStrategy(args) => (0, args.strategy_type.name()),
+        VaultInstruction::AddStrategy
+        | VaultInstruction::RemoveStrategy
+        | VaultInstruction::RemoveStrategy2(_)
+        | VaultInstruction::CollectDust => (0, ""),
+        _ => return None,
+    };
+
+    let accounts = &inst.accounts;
+
+    Some(VaultStrategyEvent {
+        instruction_type: instruction.name().to_string(),
+        vault: account_at(accounts, 0),
+        strategy: instruction.strategy().map(|position| account_at(accounts, position)).unwrap_or_default(),
+        amount,
+        strategy_type: strategy_type.to_string(),
+        authority: account_at(accounts, instruction.authority()),
+        ..Default::default()
+    })
+}
+
+// Strategy-level Dynamic Vault events: strategy lifecycle and liquidity moved between the vault and its lending strategies
+pub fn vault_strategy_events(block: &MeteoraInstructions) -> Result<VaultStrategyEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+
+        for inst in &trx.instructions {
+            let Some(mut event) = decode_strategy_event(inst) else { continue };
+
+            event.block_slot = slot;
+            event.block_time = block_time;
+            event.tx_id = tx_id.clone();
+            event.instruction_index = inst.instruction_index;
+            event.inner_instruction_index = inst.inner_instruction_index;
+
+            substreams::log::info!(
+                "Vault strategy event in slot {}: {} on vault {} strategy {} amount {}",
+                slot, event.instruction_type, event.vault, event.strategy, event.amount
+            );
+            events.push(event);
+        }
+    }
+
+    Ok(VaultStrategyEvents { events })
+}
+
+// Vault token per LP token ratio of a deposit or withdrawal, None for an empty LP amount
+fn lp_virtual_price(token_amount: u64, lp_amount: u64) -> Option<BigDecimal> {
+    if lp_amount == 0 {
+        return None;
+    }
+    Some((BigDecimal::from(token_amount) / BigDecimal::from(lp_amount)).with_prec(20))
+}
+
+// LP virtual price of every Dynamic Vault with deposits or withdrawals in the block, from the vault event logs
+pub fn vault_share_prices(block: &MeteoraInstructions) -> Result<VaultSharePrices, substreams::errors::Error> {
+    let mut prices: Vec<VaultSharePrice> = Vec::new();
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+
+        for instructions in compiled_walks(trx) {
+            for inst in instructions {
+                if inst.program_id != DYNAMIC_VAULT_PROGRAM {
+                    continue;
+                }
+                // Every vault invocation claims its logs, whether or not it moves liquidity
+                let payloads = &inst.log_events;
+                let Some(
+                    VaultInstruction::Deposit(_)
+                    | VaultInstruction::Withdraw(_)
+                    | VaultInstruction::Withdraw2(_)
+                    | VaultInstruction::WithdrawDirectlyFromStrategy(_),
+                ) = dynamic_vault::decode_instruction(inst.idl_version, &inst.data)
+                else {
+                    continue;
+                };
+                let Some(vault) = inst.accounts.first().cloned() else { continue };
+
+                let index = match prices.iter().position(|price| price.vault == vault) {
+                    Some(index) => index,
+                    None => {
+                        prices.push(VaultSharePrice {
+                            vault,
+                            block_slot: slot,
+                            block_time,
+                            ..Default::default()
+                        });
+                        prices.len() - 1
+                    }
+                };
+                let price = &mut prices[index];
+
+                for event in payloads.iter().filter_map(|payload| dynamic_vault::decode_event(inst.idl_version, payload)) {
+                    let observed = match event {
+                        VaultEvent::AddLiquidity(e) => lp_virtual_price(e.token_amount, e.lp_mint_amount),
+                        VaultEvent::RemoveLiquidity(e) => lp_virtual_price(e.token_amount, e.lp_unmint_amount),
+                        VaultEvent::TotalAmount(e) => {
+                            price.total_amount = e.total_amount;
+                            None
+                        }
+                        _ => None,
+                    };
+                    if let Some(virtual_price) = observed {
+                        price.virtual_price = virtual_price.to_string();
+                    }
+                }
+            }
+        }
+    }
+
+    // Only keep vaults with an observed price, deriving the LP supply from the vault total amount
+    prices.retain(|price| !price.virtual_price.is_empty());
+    for price in prices.iter_mut() {
+        let virtual_price = BigDecimal::try_from(price.virtual_price.as_str()).unwrap_or_default();
+        if price.total_amount > 0 && !virtual_price.is_zero() {
+            price.lp_supply = (BigDecimal::from(price.total_amount) / virtual_price).with_prec(20).to_string();
+        }
+        substreams::log::info!(
+            "Vault {} share price in slot {}: {} (total amount {})",
+            price.vault, slot, price.virtual_price, price.total_amount
+        );
+    }
+
+    Ok(VaultSharePrices { prices })
+}
+
+// Vault total amount updates and rebalance fee events of every Dynamic Vault invocation, from the vault event logs
+pub fn vault_fee_events(block: &MeteoraInstructions) -> Result<VaultFeeEvents, substreams::errors::Error> {
+    let mut output = VaultFeeEvents::default();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+
+        for instructions in compiled_walks(trx) {
+            for inst in instructions {
+                if inst.program_id != DYNAMIC_VAULT_PROGRAM {
+                    continue;
+                }
+                let payloads = &inst.log_events;
+                ordinal += 1;
+
+                let accounts = &inst.accounts;
+                let vault = account_at(accounts, 0);
+                let instruction = dynamic_vault::decode_instruction(inst.idl_version, &inst.data);
+
+                let mut fee = VaultFeeEvent {
+                    block_slot: slot,
+                    block_time,
+                    tx_id: tx_id.clone(),
+                    ordinal,
+                    instruction_type: instruction.as_ref().map(|i| i.name()).unwrap_or("unknown").to_string(),
+                    vault: vault.clone(),
+                    strategy: instruction.as_ref().and_then(|i| i.strategy()).map(|position| account_at(accounts, position)).unwrap_or_default(),
+                    fee_recipient: instruction.as_ref().and_then(|i| i.fee_vault()).map(|position| account_at(accounts, position)).unwrap_or_default(),
+                    ..Default::default()
+                };
+
+                for event in payloads.iter().filter_map(|payload| dynamic_vault::decode_event(inst.idl_version, payload)) {
+                    match event {
+                        VaultEvent::TotalAmount(e) => output.total_amounts.push(VaultTotalAmount {
+                            vault: vault.clone(),
+                            ordinal,
+                            total_amount: e.total_amount,
+                        }),
+                        VaultEvent::PerformanceFee(e) => fee.performance_fee_lp += e.lp_mint_more,
+                        VaultEvent::ReportLoss(e) => fee.loss += e.loss,
+                        _ => {}
+                    }
+                }
+
+                // Rebalances realize strategy profit, even when no fee LP gets minted
+                let is_rebalance = matches!(
+                    instruction,
+                    Some(VaultInstruction::DepositStrategy(_) | VaultInstruction::WithdrawStrategy(_))
+                );
+                if is_rebalance || fee.performance_fee_lp > 0 || fee.loss > 0 {
+                    output.fees.push(fee);
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+// Farm staking vault balance after a Farm instruction, from the balance before the transaction moved by the
+// deposits and withdrawals executed before it, tracked per transaction in `staking_vaults`
+fn farm_total_staked(
+    trx: &MeteoraTransaction,
+    staking_vaults: &mut HashMap<String, i128>,
+    accounts: &[String],
+    instruction: &FarmInstruction,
+) -> u64 {
+    let Some(staking_vault) = instruction.staking_vault().and_then(|position| accounts.get(position)) else { return 0 };
+    let balance = staking_vaults
+        .entry(staking_vault.clone())
+        .or_insert_with(|| token_accounts::pre_balance(trx, staking_vault).unwrap_or_default() as i128);
+    *balance += instruction.staked_change();
+    (*balance).max(0) as u64
+}
+
+// Farm deposits, withdrawals, claims and stake account lifecycle, with the claimed rewards and farm total staked
+pub fn farm_position_events(block: &MeteoraInstructions) -> Result<FarmPositionEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+        let mut staking_vaults = HashMap::new();
+
+        for instructions in compiled_walks(trx) {
+            for inst in instructions {
+                if inst.program_id != FARM_PROGRAM {
+                    continue;
+                }
+                let Some(instruction) = farm::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                // Every decoded Farm instruction takes an ordinal, shared with `farm_emission_events`
+                ordinal += 1;
+                let total_staked = farm_total_staked(trx, &mut staking_vaults, &inst.accounts, &instruction);
+                let Some(user) = instruction.user() else { continue };
+
+                let accounts = &inst.accounts;
+                let mut event = FarmPositionEvent {
+                    block_slot: slot,
+                    block_time,
+                    tx_id: tx_id.clone(),
+                    ordinal,
+                    instruction_type: instruction.name().to_string(),
+                    farm: account_at(accounts, 0),
+                    user: account_at(accounts, user),
+                    owner: account_at(accounts, instruction.authority()),
+                    staked_amount: "0".to_string(),
+                    ..Default::default()
+                };
+
+                if let Some(staking_vault) = instruction.staking_vault().and_then(|position| accounts.get(position)) {
+                    event.staking_mint = token_accounts::mint_from_balances(trx, staking_vault).unwrap_or_default();
+                    event.total_staked = total_staked;
+                }
+
+                match instruction {
+                    FarmInstruction::Deposit(args) => event.staked_amount = args.amount.to_string(),
+                    FarmInstruction::Withdraw(args) => event.staked_amount = format!("-{}", args.spt_amount),
+                    FarmInstruction::Claim => {
+                        // Claimed rewards are the transaction balance change of the owner reward accounts
+                        let Some((reward_a, reward_b)) = instruction.reward_accounts() else { continue };
+                        for (position, amount, mint) in [
+                            (reward_a, &mut event.reward_a_claimed, &mut event.reward_a_mint),
+                            (reward_b, &mut event.reward_b_claimed, &mut event.reward_b_mint),
+                        ] {
+                            let Some(account) = accounts.get(position) else { continue };
+                            *amount = token_accounts::balance_change(trx, account).unwrap_or_default().max(0) as u64;
+                            *mint = token_accounts::mint_from_balances(trx, account).unwrap_or_default();
+                        }
+                    }
+                    _ => {}
+                }
+
+                events.push(event);
+            }
+        }
+    }
+
+    Ok(FarmPositionEvents { events })
+}
+
+// Farm creations, reward fundings and reward duration updates
+pub fn farm_emission_events(block: &MeteoraInstructions) -> Result<FarmEmissionEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+        let mut staking_vaults = HashMap::new();
+
+        for instructions in compiled_walks(trx) {
+            for inst in instructions {
+                if inst.program_id != FARM_PROGRAM {
+                    continue;
+                }
+                let Some(instruction) = farm::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                // Every decoded Farm instruction takes an ordinal, shared with `farm_position_events`
+                ordinal += 1;
+                let total_staked = farm_total_staked(trx, &mut staking_vaults, &inst.accounts, &instruction);
+
+                let accounts = &inst.accounts;
+                let mint_of = |position: usize| {
+                    accounts
+                        .get(position)
+                        .and_then(|account| token_accounts::mint_from_balances(trx, account))
+                        .unwrap_or_default()
+                };
+                let mut event = FarmEmissionEvent {
+                    block_slot: slot,
+                    block_time,
+                    tx_id: tx_id.clone(),
+                    instruction_type: instruction.name().to_string(),
+                    farm: account_at(accounts, 0),
+                    authority: account_at(accounts, instruction.authority()),
+                    ordinal,
+                    total_staked,
+                    ..Default::default()
+                };
+
+                match instruction {
+                    FarmInstruction::InitializePool(args) => {
+                        event.staking_mint = account_at(accounts, 1);
+                        event.reward_a_mint = account_at(accounts, 3);
+                        event.reward_b_mint = account_at(accounts, 5);
+                        event.reward_duration = args.reward_duration;
+                        substreams::log::info!("New farm {} created in slot {}", event.farm, slot);
+                    }
+                    FarmInstruction::Fund(args) => {
+                        event.staking_mint = mint_of(1);
+                        event.reward_a_mint = mint_of(2);
+                        event.reward_b_mint = mint_of(3);
+                        event.amount_a = args.amount_a;
+                        event.amount_b = args.amount_b;
+                    }
+                    FarmInstruction::UpdateRewardDuration(args) => event.reward_duration = args.new_reward_duration,
+                    _ => continue,
+                }
+
+                events.push(event);
+            }
+        }
+    }
+
+    Ok(FarmEmissionEvents { events })
+}
+
+// Instructions invoked through CPI by the instruction at `index` of a compiled instruction walk
+// (the compiled instruction followed by its inner instructions)
+fn cpi_children(instructions: &[MeteoraInstruction], index: usize) -> &[MeteoraInstruction] {
+    if !instructions[index].is_inner_instruction {
+        return &instructions[index + 1..];
+    }
+
+    let depth = instructions[index].stack_height;
+    let children = instructions[index + 1..]
+        .iter()
+        .take_while(|inst| inst.stack_height > depth)
+        .count();
+    &instructions[index + 1..index + 1 + children]
+}
+
+// Top-level instructions a zap links when it runs after `set_token_ledger`: the swaps executed between the ledger
+// snapshot and the zap, typically the Jupiter route whose output the zap deposits
+fn token_ledger_siblings<'a, 'b>(walks: &'b [&'a [MeteoraInstruction]], walk: usize) -> &'b [&'a [MeteoraInstruction]] {
+    let zap_root = |instructions: &[MeteoraInstruction]| {
+        let root = instructions.first().filter(|root| !root.is_inner_instruction && root.program_id == ZAP_PROGRAM)?;
+        zap::decode_instruction(root.idl_version, &root.data)
+    };
+    // The ledger must be set after any earlier zap, whose own siblings come before it
+    let start = walks[..walk]
+        .iter()
+        .rposition(|instructions| zap_root(instructions).is_some_and(|zap| zap == zap::ZapInstruction::SetTokenLedger || zap.direction().is_some()));
+    match start {
+        Some(start) if zap_root(walks[start]) == Some(zap::ZapInstruction::SetTokenLedger) => &walks[start + 1..walk],
+        _ => &[],
+    }
+}
+
+// Record the swaps and liquidity changes among the instructions linked to a zap. The zap input is the input of the
+// first outermost swap and its output the output of the last one, rather than swaps nested in a Jupiter route.
+fn add_zap_steps(event: &mut ZapEvent, trx: &MeteoraTransaction, instructions: &[MeteoraInstruction]) {
+    let mut nested_until = 0;
+
+    for index in 0..instructions.len() {
+        let swaps = event.swaps.len();
+        add_zap_step(event, trx, instructions, index);
+        let Some(swap) = event.swaps.get(swaps) else { continue };
+        if index < nested_until {
+            continue;
+        }
+
+        if event.input_mint.is_empty() {
+            event.input_mint = swap.input_mint.clone();
+            event.input_amount = swap.amount_in;
+        }
+        event.output_mint = swap.output_mint.clone();
+        event.output_amount = swap.amount_out;
+        nested_until = index + 1 + cpi_children(instructions, index).len();
+    }
+}
+
+// Record a swap or liquidity instruction linked to a zap. Swap amounts come from the token transfers the swap
+// performed: the first one is its input and the last one of another mint its output.
+fn add_zap_step(event: &mut ZapEvent, trx: &MeteoraTransaction, instructions: &[MeteoraInstruction], index: usize) {
+    let inst = &instructions[index];
+    let program_id = &inst.program_id;
+    let accounts = &inst.accounts;
+    let mint_of = |position: usize| {
+        accounts
+            .get(position)
+            .and_then(|account| token_accounts::mint_from_balances(trx, account))
+            .unwrap_or_default()
+    };
+    let transfers: Vec<_> = cpi_children(instructions, index).iter().filter_map(token_accounts::transfer).collect();
+    let transfer_mint = |transfer: &token_accounts::TokenTransfer| match transfer.mint {
+        Some(mint) => mint.to_string(),
+        None => token_accounts::mint_from_balances(trx, transfer.source).unwrap_or_default(),
+    };
+    let transferred = |swap: &mut ZapSwap| {
+        let Some(input) = transfers.first() else { return };
+        swap.amount_in = input.amount;
+        swap.input_mint = transfer_mint(input);
+        if let Some((output, mint)) = transfers
+            .iter()
+            .rev()
+            .map(|transfer| (transfer, transfer_mint(transfer)))
+            .find(|(_, mint)| *mint != swap.input_mint)
+        {
+            swap.amount_out = output.amount;
+            swap.output_mint = mint;
+        }
+    };
+    let swap = |program: &str, instruction_type: &str, pool: usize, amount_in: u64, input: usize, output: usize| {
+        let mut swap = ZapSwap {
+            program: program.to_string(),
+            instruction_type: instruction_type.to_string(),
+            pool: account_at(accounts, pool),
+            amount_in,
+            input_mint: mint_of(input),
+            output_mint: mint_of(output),
+            ..Default::default()
+        };
+        transferred(&mut swap);
+        swap
+    };
+    let liquidity = |program: &str, instruction_type: &str, pool: usize, position: usize| ZapLiquidity {
+        program: program.to_string(),
+        instruction_type: instruction_type.to_string(),
+        pool: account_at(accounts, pool),
+        position: account_at(accounts, position),
+        ..Default::default()
+    };
+
+    match program_id.as_str() {
+        DLMM_PROGRAM => {
+            let Some(instruction) = dlmm::decode_instruction(inst.idl_version, &inst.data) else { return };
+            let name = instruction.name();
+            let pair = instruction.lb_pair();
+            match &instruction {
+                PairInstruction::Swap(args) | PairInstruction::Swap2(args) => {
+                    event.swaps.push(swap("dlmm", name, pair, args.amount_in, 4, 5))
+                }
+                PairInstruction::SwapExactOut(args) | PairInstruction::SwapExactOut2(args) => {
+                    event.swaps.push(swap("dlmm", name, pair, args.max_in_amount, 4, 5))
+                }
+                PairInstruction::SwapWithPriceImpact(args) | PairInstruction::SwapWithPriceImpact2(args) => {
+                    event.swaps.push(swap("dlmm", name, pair, args.amount_in, 4, 5))
+                }
+                _ => {
+                    let Some(position) = instruction.position() else { return };
+                    let (amount_a, amount_b) = match &instruction {
+                        PairInstruction::AddLiquidity(args) => (args.amount_x, args.amount_y),
+                        PairInstruction::AddLiquidityByWeight(args) => (args.amount_x, args.amount_y),
+                        PairInstruction::AddLiquidityByStrategy(args) => (args.amount_x, args.amount_y),
+                        PairInstruction::AddLiquidityOneSide(args) => (args.amount, 0),
+                        PairInstruction::AddLiquidityByStrategyOneSide(args) => (args.amount, 0),
+                        PairInstruction::RemoveLiquidity(_)
+                        | PairInstruction::RemoveLiquidityByRange(_)
+                        | PairInstruction::RemoveAllLiquidity => (0, 0),
+                        _ => return,
+                    };
+                    event.liquidity.push(ZapLiquidity {
+                        amount_a,
+                        amount_b,
+                        ..liquidity("dlmm", name, pair, position)
+                    });
+                }
+            }
+        }
+        DAMM_V1_PROGRAM => {
+            let Some(instruction) = damm_v1::decode_instruction(inst.idl_version, &inst.data) else { return };
+            let name = instruction.name();
+            match instruction {
+                damm_v1::PoolInstruction::Swap(args) => event.swaps.push(swap("damm_v1", name, 0, args.in_amount, 1, 2)),
+                damm_v1::PoolInstruction::AddBalanceLiquidity(args) | damm_v1::PoolInstruction::RemoveBalanceLiquidity(args) => {
+                    event.liquidity.push(ZapLiquidity {
+                        amount_a: args.token_a_amount,
+                        amount_b: args.token_b_amount,
+                        ..liquidity("damm_v1", name, 0, 2)
+                    })
+                }
+                damm_v1::PoolInstruction::AddImbalanceLiquidity(args) => event.liquidity.push(ZapLiquidity {
+                    amount_a: args.token_a_amount,
+                    amount_b: args.token_b_amount,
+                    ..liquidity("damm_v1", name, 0, 2)
+                }),
+                damm_v1::PoolInstruction::RemoveLiquiditySingleSide(_) => event.liquidity.push(liquidity("damm_v1", name, 0, 2)),
+            }
+        }
+        DAMM_V2_PROGRAM => {
+            let Some(instruction) = damm_v2::decode_instruction(inst.idl_version, &inst.data) else { return };
+            let name = instruction.name();
+            let pool = instruction.pool();
+            match &instruction {
+                damm_v2::PoolInstruction::Swap(args) => event.swaps.push(swap("damm_v2", name, pool, args.amount_in, 2, 3)),
+                damm_v2::PoolInstruction::AddLiquidity(args) | damm_v2::PoolInstruction::RemoveLiquidity(args) => {
+                    let Some(position) = instruction.position() else { return };
+                    event.liquidity.push(ZapLiquidity {
+                        liquidity: args.liquidity_delta.to_string(),
+                        ..liquidity("damm_v2", name, pool, position)
+                    });
+                }
+                damm_v2::PoolInstruction::RemoveAllLiquidity(_) => {
+                    let Some(position) = instruction.position() else { return };
+                    event.liquidity.push(liquidity("damm_v2", name, pool, position));
+                }
+                _ => {}
+            }
+        }
+        zap::JUPITER_PROGRAM => {
+            let Some(name) = zap::jupiter_route_name(&inst.data) else { return };
+            let mut swap = ZapSwap {
+                program: "jupiter".to_string(),
+                instruction_type: name.to_string(),
+                ..Default::default()
+            };
+            transferred(&mut swap);
+            event.swaps.push(swap);
+        }
+        _ => {}
+    }
+}
+
+// Zap deposits and withdrawals decomposed into the swaps and liquidity changes they performed
+pub fn zap_events(block: &MeteoraInstructions) -> Result<ZapEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+        let user = &trx.fee_payer;
+        let walks: Vec<&[MeteoraInstruction]> = compiled_walks(trx).collect();
+
+        for (walk, instructions) in walks.iter().enumerate() {
+            for (index, inst) in instructions.iter().enumerate() {
+                if inst.program_id != ZAP_PROGRAM {
+                    continue;
+                }
+                let Some(instruction) = zap::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                let Some(direction) = instruction.direction() else { continue };
+
+                let mut event = ZapEvent {
+                    block_slot: slot,
+                    block_time,
+                    tx_id: tx_id.clone(),
+                    instruction_index: inst.instruction_index,
+                    inner_instruction_index: inst.inner_instruction_index,
+                    instruction_type: instruction.name().to_string(),
+                    direction: direction.to_string(),
+                    user: user.clone(),
+                    ..Default::default()
+                };
+
+                if !inst.is_inner_instruction {
+                    for sibling in token_ledger_siblings(&walks, walk) {
+                        add_zap_steps(&mut event, trx, sibling);
+                    }
+                }
+                add_zap_steps(&mut event, trx, cpi_children(instructions, index));
+
+                if let Some(last) = event.liquidity.last() {
+                    event.position = last.position.clone();
+                    // DAMM v1 liquidity is an LP token balance rather than a position account
+                    if last.program == "damm_v1" {
+                        event.lp_mint = token_accounts::mint_from_balances(trx, &last.position).unwrap_or_default();
+                        event.lp_amount = token_accounts::balance_change(trx, &last.position).unwrap_or_default() as i64;
+                    }
+                }
+
+                substreams::log::info!(
+                    "Zap {} in slot {}: {} {} of {} -> {} {}, {} swaps, {} liquidity changes",
+                    event.direction, slot, event.instruction_type, event.input_amount, event.input_mint,
+                    event.output_amount, event.output_mint, event.swaps.len(), event.liquidity.len()
+                );
+                events.push(event);
+            }
+        }
+    }
+
+    Ok(ZapEvents { events })
+}
+
+// DLMM position lifecycle: initialization, liquidity changes with their per-bin shares, fee and reward claims, and close
+pub fn dlmm_position_events(block: &MeteoraInstructions) -> Result<DlmmPositionEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+
+        for instructions in compiled_walks(trx) {
+            for (index, inst) in instructions.iter().enumerate() {
+                if inst.program_id != DLMM_PROGRAM {
+                    continue;
+                }
+                let Some(instruction) = dlmm::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                let Some(position) = instruction.position() else { continue };
+                ordinal += 1;
+
+                let accounts = &inst.accounts;
+                let mut event = DlmmPositionEvent {
+                    block_slot: slot,
+                    block_time,
+                    tx_id: tx_id.clone(),
+                    ordinal,
+                    instruction_type: instruction.name().to_string(),
+                    lb_pair: account_at(accounts, instruction.lb_pair()),
+                    position: account_at(accounts, position),
+                    owner: account_at(accounts, instruction.authority()),
+                    ..Default::default()
+                };
+
+                match &instruction {
+                    PairInstruction::InitializePosition(args)
+                    | PairInstruction::InitializePositionPda(args)
+                    | PairInstruction::InitializePositionByOperator(args) => {
+                        let Some(upper_bin_id) = args.lower_bin_id.checked_add(args.width).and_then(|end| end.checked_sub(1)) else {
+                            substreams::log::info!(
+                                "Skipping DLMM {} in {}: bin range from {} with width {} overflows",
+                                event.instruction_type, tx_id, args.lower_bin_id, args.width
+                            );
+                            continue;
+                        };
+                        event.lower_bin_id = args.lower_bin_id;
+                        event.upper_bin_id = upper_bin_id;
+                    }
+                    PairInstruction::AddLiquidity(args) => {
+                        event.bins = args
+                            .bin_liquidity_dist
+                            .iter()
+                            .map(|bin| DlmmBinLiquidity {
+                                bin_id: bin.bin_id,
+                                distribution_x: bin.distribution_x as u32,
+                                distribution_y: bin.distribution_y as u32,
+                                ..Default::default()
+                            })
+                            .collect();
+                    }
+                    PairInstruction::AddLiquidityByWeight(args) => {
+                        event.bins = args
+                            .bin_liquidity_dist
+                            .iter()
+                            .map(|bin| DlmmBinLiquidity {
+                                bin_id: bin.bin_id,
+                                weight: bin.weight as u32,
+                                ..Default::default()
+                            })
+                            .collect();
+                    }
+                    PairInstruction::AddLiquidityOneSide(args) => {
+                        event.bins = args
+                            .bin_liquidity_dist
+                            .iter()
+                            .map(|bin| DlmmBinLiquidity {
+                                bin_id: bin.bin_id,
+                                weight: bin.weight as u32,
+                                ..Default::default()
+                            })
+                            .collect();
+                    }
+                    PairInstruction::AddLiquidityByStrategy(args) => {
+                        event.strategy_min_bin_id = args.strategy_parameters.min_bin_id;
+                        event.strategy_max_bin_id = args.strategy_parameters.max_bin_id;
+                        event.strategy_type = args.strategy_parameters.strategy_type as u32;
+                    }
+                    PairInstruction::AddLiquidityByStrategyOneSide(args) => {
+                        event.strategy_min_bin_id = args.strategy_parameters.min_bin_id;
+                        event.strategy_max_bin_id = args.strategy_parameters.max_bin_id;
+                        event.strategy_type = args.strategy_parameters.strategy_type as u32;
+                    }
+                    PairInstruction::RemoveLiquidity(args) => {
+                        event.bins = args
+                            .bin_liquidity_removal
+                            .iter()
+                            .map(|bin| DlmmBinLiquidity {
+                                bin_id: bin.bin_id,
+                                bps_to_remove: bin.bps_to_remove as u32,
+                                ..Default::default()
+                            })
+                            .collect();
+                    }
+                    PairInstruction::RemoveLiquidityByRange(args) => {
+                        event.bins = (args.from_bin_id..=args.to_bin_id)
+                            .map(|bin_id| DlmmBinLiquidity {
+                                bin_id,
+                                bps_to_remove: args.bps_to_remove as u32,
+                                ..Default::default()
+                            })
+                            .collect();
+                    }
+                    PairInstruction::ClaimReward(args) => event.reward_index = args.reward_index,
+                    _ => {}
+                }
+
+                // Amounts and owners come from the events the pair emits through self-CPI
+                for cpi in cpi_children(instructions, index) {
+                    if cpi.program_id != DLMM_PROGRAM {
+                        continue;
+                    }
+                    match dlmm::decode_event(cpi.idl_version, &cpi.data) {
+                        Some(PairEvent::PositionCreate(e)) => event.owner = bs58::encode(e.owner).into_string(),
+                        Some(PairEvent::PositionClose(e)) => event.owner = bs58::encode(e.owner).into_string(),
+                        Some(PairEvent::AddLiquidity(e)) => {
+                            event.amount_x_added += e.amounts[0];
+                            event.amount_y_added += e.amounts[1];
+                            event.active_bin_id = e.active_bin_id;
+                        }
+                        Some(PairEvent::RemoveLiquidity(e)) => {
+                            event.amount_x_removed += e.amounts[0];
+                            event.amount_y_removed += e.amounts[1];
+                            event.active_bin_id = e.active_bin_id;
+                        }
+                        Some(PairEvent::ClaimFee(e)) => {
+                            event.owner = bs58::encode(e.owner).into_string();
+                            event.fee_x_claimed += e.fee_x;
+                            event.fee_y_claimed += e.fee_y;
+                        }
+                        Some(PairEvent::ClaimReward(e)) => {
+                            event.owner = bs58::encode(e.owner).into_string();
+                            event.reward_claimed += e.total_reward;
+                        }
+                        _ => {}
+                    }
+                }
+
+                events.push(event);
+            }
+        }
+    }
+
+    Ok(DlmmPositionEvents { events })
+}
+
+// DLMM swaps with their bins, amounts and fees, and pair creations with their bin step
+pub fn dlmm_swap_events(block: &MeteoraInstructions) -> Result<DlmmSwapEvents, substreams::errors::Error> {
+    let mut output = DlmmSwapEvents::default();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+
+        for instructions in compiled_walks(trx) {
+            for (index, inst) in instructions.iter().enumerate() {
+                if inst.program_id != DLMM_PROGRAM {
+                    continue;
+                }
+                // Pairs can be created by initialize instructions without a decoder; their events still are
+                let instruction = dlmm::decode_instruction(inst.idl_version, &inst.data);
+                let is_swap = instruction.as_ref().is_some_and(|instruction| instruction.is_swap());
+                let accounts = &inst.accounts;
+                let decimals_of = |position: usize| {
+                    accounts
+                        .get(position)
+                        .and_then(|account| token_accounts::decimals_from_balances(trx, account))
+                        .unwrap_or_default()
+                };
+
+                for cpi in cpi_children(instructions, index) {
+                    if cpi.program_id != DLMM_PROGRAM {
+                        continue;
+                    }
+                    match dlmm::decode_event(cpi.idl_version, &cpi.data) {
+                        Some(PairEvent::Swap(e)) if is_swap => {
+                            ordinal += 1;
+                            output.swaps.push(DlmmSwap {
+                                block_slot: slot,
+                                block_time,
+                                tx_id: tx_id.clone(),
+                                ordinal,
+                                instruction_type: instruction.as_ref().map(|i| i.name()).unwrap_or_default().to_string(),
+                                lb_pair: bs58::encode(e.lb_pair).into_string(),
+                                user: bs58::encode(e.from).into_string(),
+                                // Swap accounts: reserve_x 2, reserve_y 3, token_x_mint 6, token_y_mint 7
+                                token_x_mint: account_at(accounts, 6),
+                                token_y_mint: account_at(accounts, 7),
+                                token_x_decimals: decimals_of(2),
+                                token_y_decimals: decimals_of(3),
+                                start_bin_id: e.start_bin_id,
+                                end_bin_id: e.end_bin_id,
+                                amount_in: e.amount_in,
+                                amount_out: e.amount_out,
+                                swap_for_y: e.swap_for_y,
+                                fee: e.fee,
+                                protocol_fee: e.protocol_fee,
+                                host_fee: e.host_fee,
+                                fee_bps: e.fee_bps.to_string(),
+                            });
+                        }
+                        Some(PairEvent::LbPairCreate(e)) => {
+                            ordinal += 1;
+                            output.pairs.push(DlmmPairCreated {
+                                block_slot: slot,
+                                block_time,
+                                ordinal,
+                                lb_pair: bs58::encode(e.lb_pair).into_string(),
+                                bin_step: e.bin_step as u32,
+                                token_x_mint: bs58::encode(e.token_x).into_string(),
+                                token_y_mint: bs58::encode(e.token_y).into_string(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+// DLMM fee parameters set by preset creation, pair creation from a preset and base fee updates
+pub fn dlmm_fee_parameter_events(block: &MeteoraInstructions) -> Result<DlmmFeeParameterEvents, substreams::errors::Error> {
+    let mut events = Vec::new();
+    let mut ordinal = 0u64;
+
+    for trx in &block.transactions {
+        for inst in &trx.instructions {
+            if inst.program_id != DLMM_PROGRAM {
+                continue;
+            }
+            let Some(instruction) = dlmm::decode_instruction(inst.idl_version, &inst.data) else { continue };
+            let mut event = DlmmFeeParameterEvent {
+                block_slot: block.block_slot,
+                block_time: block.block_time,
+                tx_id: trx.tx_id.clone(),
+                instruction_type: instruction.name().to_string(),
+                ..Default::default()
+            };
+
+            match &instruction {
+                PairInstruction::InitializePresetParameter(args) => {
+                    event.preset_parameter = account_at(&inst.accounts, 0);
+                    event.bin_step = args.bin_step as u32;
+                    event.base_factor = args.base_factor as u32;
+                    event.variable_fee_control = args.variable_fee_control;
+                    event.max_volatility_accumulator = args.max_volatility_accumulator;
+                    event.protocol_share = args.protocol_share as u32;
+                }
+                // lb_pair 0, preset_parameter 7
+                PairInstruction::InitializeLbPair(args) => {
+                    event.lb_pair = account_at(&inst.accounts, 0);
+                    event.preset_parameter = account_at(&inst.accounts, 7);
+                    event.bin_step = args.bin_step as u32;
+                }
+                PairInstruction::UpdateFeeParameters(args) => {
+                    event.lb_pair = account_at(&inst.accounts, 0);
+                    event.base_factor = args.base_factor as u32;
+                    event.protocol_share = args.protocol_share as u32;
+                }
+                _ => continue,
+            }
+
+            ordinal += 1;
+            event.ordinal = ordinal;
+            events.push(event);
+        }
+    }
+
+    Ok(DlmmFeeParameterEvents { events })
+}
+
+fn token_units(amount: u64, decimals: u32) -> BigDecimal {
+    BigDecimal::from(amount) / BigDecimal::from(10u64.pow(decimals.min(19)))
+}
+
+// Normalized swap, priced in quote per base once its amounts and decimals are known
+#[allow(clippy::too_many_arguments)]
+fn pool_swap(
+    protocol: &str,
+    pool: String,
+    user: String,
+    (base_mint, base_decimals, base_amount): (String, u32, u64),
+    (quote_mint, quote_decimals, quote_amount): (String, u32, u64),
+    is_buy: bool,
+) -> PoolSwap {
+    let base_volume = token_units(base_amount, base_decimals);
+    let quote_volume = token_units(quote_amount, quote_decimals);
+    let price = if base_amount > 0 {
+        (quote_volume.clone() / base_volume.clone()).with_prec(20).to_string()
+    } else {
+        String::new()
+    };
+
+    PoolSwap {
+        protocol: protocol.to_string(),
+        pool,
+        user,
+        base_mint,
+        quote_mint,
+        base_decimals,
+        quote_decimals,
+        base_amount,
+        quote_amount,
+        is_buy,
+        price,
+        base_volume: base_volume.to_string(),
+        quote_volume: quote_volume.to_string(),
+        ..Default::default()
+    }
+}
+
+// Fee split of a swap, as raw amounts of `fee_mint`: [lp, protocol, partner, referral]
+fn set_swap_fees(
+    swap: &mut PoolSwap,
+    fee_mint: String,
+    [lp_fee, protocol_fee, partner_fee, referral_fee]: [u64; 4],
+    referrer: String,
+) {
+    swap.fee_mint = fee_mint;
+    swap.lp_fee = lp_fee;
+    swap.protocol_fee = protocol_fee;
+    swap.partner_fee = partner_fee;
+    swap.referral_fee = referral_fee;
+    swap.referrer = referrer;
+}
+
+// Swaps of every DLMM, DAMM v1, DAMM v2 and Dynamic Bonding Curve pool, from the swap events of each program
+pub fn pool_swaps(block: &MeteoraInstructions) -> Result<PoolSwaps, substreams::errors::Error> {
+    let mut swaps = Vec::new();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+        let user = &trx.fee_payer;
+
+        for instructions in compiled_walks(trx) {
+            for (index, inst) in instructions.iter().enumerate() {
+                let program_id = &inst.program_id;
+                let accounts = &inst.accounts;
+                let mint_of = |position: usize| {
+                    accounts
+                        .get(position)
+                        .and_then(|account| token_accounts::mint_from_balances(trx, account))
+                        .unwrap_or_default()
+                };
+                let decimals_of = |position: usize| {
+                    accounts
+                        .get(position)
+                        .and_then(|account| token_accounts::decimals_from_balances(trx, account))
+                        .unwrap_or_default()
+                };
+                let self_cpis = || {
+                    cpi_children(instructions, index)
+                        .iter()
+                        .filter(|cpi| cpi.program_id == *program_id)
+                };
+                // Owner of the referral or host fee token account, when the swap paid it a fee
+                let reserve_of = |position: usize| {
+                    accounts
+                        .get(position)
+                        .and_then(|account| token_accounts::post_balance(trx, account))
+                        .unwrap_or_default()
+                };
+                let referrer_of = |position: usize, fee: u64| {
+                    let Some(account) = accounts.get(position).filter(|_| fee > 0) else { return String::new() };
+                    token_accounts::owner_from_balances(trx, account).unwrap_or_else(|| account.clone())
+                };
+
+                let mut swap = match program_id.as_str() {
+                    DLMM_PROGRAM => {
+                        let Some(instruction) = dlmm::decode_instruction(inst.idl_version, &inst.data) else { continue };
+                        if !instruction.is_swap() {
+                            continue;
+                        }
+                        let Some(e) = self_cpis().find_map(|cpi| match dlmm::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(PairEvent::Swap(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        let (base_amount, quote_amount) =
+                            if e.swap_for_y { (e.amount_in, e.amount_out) } else { (e.amount_out, e.amount_in) };
+                        let mut swap = pool_swap(
+                            "dlmm",
+                            account_at(accounts, 0),
+                            bs58::encode(e.from).into_string(),
+                            (account_at(accounts, 6), decimals_of(2), base_amount),
+                            (account_at(accounts, 7), decimals_of(3), quote_amount),
+                            !e.swap_for_y,
+                        );
+                        (swap.base_reserve, swap.quote_reserve) = (reserve_of(2), reserve_of(3));
+                        swap.active_bin_id = e.end_bin_id;
+                        swap.bins_crossed = e.end_bin_id.abs_diff(e.start_bin_id);
+                        // `fee` is the total input fee; the host share of the protocol fee goes to host_fee_in 9
+                        let fee_mint = if e.swap_for_y { account_at(accounts, 6) } else { account_at(accounts, 7) };
+                        let lp_fee = e.fee.saturating_sub(e.protocol_fee).saturating_sub(e.host_fee);
+                        set_swap_fees(&mut swap, fee_mint, [lp_fee, e.protocol_fee, 0, e.host_fee], referrer_of(9, e.host_fee));
+                        swap
+                    }
+                    DAMM_V1_PROGRAM => {
+                        let payloads = &inst.log_events;
+                        let Some(damm_v1::PoolInstruction::Swap(_)) = damm_v1::decode_instruction(inst.idl_version, &inst.data) else {
+                            continue;
+                        };
+                        let Some(e) = payloads.iter().find_map(|payload| match damm_v1::decode_event(inst.idl_version, payload) {
+                            Some(damm_v1::PoolEvent::Swap(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        // a_token_vault 5 and b_token_vault 6 hold the pool tokens; user_source 1 tells the direction
+                        let (mint_a, mint_b) = (mint_of(5), mint_of(6));
+                        let a_to_b = mint_of(1) == mint_a;
+                        let (base_amount, quote_amount) =
+                            if a_to_b { (e.in_amount, e.out_amount) } else { (e.out_amount, e.in_amount) };
+                        let fee_mint = if a_to_b { mint_a.clone() } else { mint_b.clone() };
+                        let mut swap = pool_swap(
+                            "damm_v1",
+                            account_at(accounts, 0),
+                            account_at(accounts, 12),
+                            (mint_a, decimals_of(5), base_amount),
+                            (mint_b, decimals_of(6), quote_amount),
+                            !a_to_b,
+                        );
+                        // Fees are charged on the input token; the optional host fee account follows the 15 named accounts
+                        let fees = [e.trade_fee, e.protocol_fee, 0, e.host_fee];
+                        set_swap_fees(&mut swap, fee_mint, fees, referrer_of(15, e.host_fee));
+                        swap
+                    }
+                    DAMM_V2_PROGRAM => {
+                        let Some(damm_v2::PoolInstruction::Swap(_)) = damm_v2::decode_instruction(inst.idl_version, &inst.data) else {
+                            continue;
+                        };
+                        let Some(e) = self_cpis().find_map(|cpi| match damm_v2::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(damm_v2::PoolEvent::Swap(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        let a_to_b = e.trade_direction == 0;
+                        let output = e.swap_result.output_amount;
+                        let (base_amount, quote_amount) =
+                            if a_to_b { (e.actual_amount_in, output) } else { (output, e.actual_amount_in) };
+                        let mut swap = pool_swap(
+                            "damm_v2",
+                            account_at(accounts, 1),
+                            account_at(accounts, 8),
+                            (account_at(accounts, 6), decimals_of(4), base_amount),
+                            (account_at(accounts, 7), decimals_of(5), quote_amount),
+                            !a_to_b,
+                        );
+                        (swap.base_reserve, swap.quote_reserve) = (reserve_of(4), reserve_of(5));
+                        // Fees are charged on the output token, except on pools collecting fees in token B only
+                        // where a B to A swap pays them in token B; the pool fee mode is not known here
+                        let fee_mint = if a_to_b { account_at(accounts, 7) } else { account_at(accounts, 6) };
+                        let result = &e.swap_result;
+                        let fees = [result.lp_fee, result.protocol_fee, result.partner_fee, result.referral_fee];
+                        set_swap_fees(&mut swap, fee_mint, fees, referrer_of(11, result.referral_fee));
+                        swap
+                    }
+                    DBC_PROGRAM => {
+                        let Some(dbc::CurveInstruction::Swap(_)) = dbc::decode_instruction(inst.idl_version, &inst.data) else {
+                            continue;
+                        };
+                        let Some(e) = self_cpis().find_map(|cpi| match dbc::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(dbc::CurveEvent::Swap(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        let base_to_quote = e.trade_direction == 0;
+                        let (input, output) = (e.swap_result.actual_input_amount, e.swap_result.output_amount);
+                        let (base_amount, quote_amount) = if base_to_quote { (input, output) } else { (output, input) };
+                        let mut swap = pool_swap(
+                            "dbc",
+                            account_at(accounts, 2),
+                            account_at(accounts, 9),
+                            (account_at(accounts, 7), decimals_of(5), base_amount),
+                            (account_at(accounts, 8), decimals_of(6), quote_amount),
+                            !base_to_quote,
+                        );
+                        (swap.base_reserve, swap.quote_reserve) = (reserve_of(5), reserve_of(6));
+                        // Curve fees are always collected in the quote token; the trading fee is shared by the
+                        // partner and the creator according to the pool config
+                        let result = &e.swap_result;
+                        let fees = [0, result.protocol_fee, result.trading_fee, result.referral_fee];
+                        set_swap_fees(&mut swap, account_at(accounts, 8), fees, referrer_of(12, result.referral_fee));
+                        swap
+                    }
+                    _ => continue,
+                };
+
+                ordinal += 1;
+                swap.block_slot = slot;
+                swap.block_time = block_time;
+                swap.tx_id = tx_id.clone();
+                swap.ordinal = ordinal;
+                if swap.user.is_empty() {
+                    swap.user = user.clone();
+                }
+                swaps.push(swap);
+            }
+        }
+    }
+
+    Ok(PoolSwaps { swaps })
+}
+
+// Pools created on DLMM, DAMM v1, DAMM v2 and the Dynamic Bonding Curve, from each program's pool creation event
+pub fn pools_created(block: &MeteoraInstructions) -> Result<PoolsCreated, substreams::errors::Error> {
+    let mut pools = Vec::new();
+    let mut ordinal = 0u64;
+    let slot = block.block_slot;
+    let block_time = block.block_time;
+
+    for trx in &block.transactions {
+        let tx_id = &trx.tx_id;
+        let fee_payer = &trx.fee_payer;
+
+        for instructions in compiled_walks(trx) {
+            for (index, inst) in instructions.iter().enumerate() {
+                let program_id = &inst.program_id;
+                let accounts = &inst.accounts;
+                let self_cpis = || {
+                    cpi_children(instructions, index)
+                        .iter()
+                        .filter(|cpi| cpi.program_id == *program_id)
+                };
+
+                let mut pool = match program_id.as_str() {
+                    DLMM_PROGRAM => {
+                        let Some(e) = self_cpis().find_map(|cpi| match dlmm::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(PairEvent::LbPairCreate(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        // Only `initialize_lb_pair` is decoded; the permissioned and customizable variants keep the fee payer
+                        let (instruction, creator, config) = match dlmm::decode_instruction(inst.idl_version, &inst.data) {
+                            Some(instruction @ PairInstruction::InitializeLbPair(_)) => {
+                                (instruction.name().to_string(), account_at(accounts, instruction.authority()), account_at(accounts, 7))
+                            }
+                            _ => (String::new(), String::new(), String::new()),
+                        };
+                        PoolCreated {
+                            protocol: "dlmm".to_string(),
+                            pool: bs58::encode(e.lb_pair).into_string(),
+                            instruction,
+                            token_a_mint: bs58::encode(e.token_x).into_string(),
+                            token_b_mint: bs58::encode(e.token_y).into_string(),
+                            creator,
+                            config,
+                            bin_step: e.bin_step as u32,
+                            ..Default::default()
+                        }
+                    }
+                    DAMM_V1_PROGRAM => {
+                        let payloads = &inst.log_events;
+                        let Some(e) = payloads.iter().find_map(|payload| match damm_v1::decode_event(inst.idl_version, payload) {
+                            Some(damm_v1::PoolEvent::PoolCreated(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        PoolCreated {
+                            protocol: "damm_v1".to_string(),
+                            pool: bs58::encode(e.pool).into_string(),
+                            token_a_mint: bs58::encode(e.token_a_mint).into_string(),
+                            token_b_mint: bs58::encode(e.token_b_mint).into_string(),
+                            lp_mint: bs58::encode(e.lp_mint).into_string(),
+                            permissioned: e.pool_type == 0,
+                            ..Default::default()
+                        }
+                    }
+                    DAMM_V2_PROGRAM => {
+                        let Some(e) = self_cpis().find_map(|cpi| match damm_v2::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(damm_v2::PoolEvent::InitializePool(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        let instruction = damm_v2::decode_instruction(inst.idl_version, &inst.data);
+                        // Fee numerators are over 1_000_000_000, that is 100_000 per basis point
+                        let fee_bps = BigDecimal::from(e.base_fee.cliff_fee_numerator) / BigDecimal::from(100_000u64);
+                        PoolCreated {
+                            protocol: "damm_v2".to_string(),
+                            pool: bs58::encode(e.pool).into_string(),
+                            instruction: instruction.as_ref().map(|i| i.name().to_string()).unwrap_or_default(),
+                            token_a_mint: bs58::encode(e.token_a_mint).into_string(),
+                            token_b_mint: bs58::encode(e.token_b_mint).into_string(),
+                            creator: bs58::encode(e.creator).into_string(),
+                            config: instruction.and_then(|i| i.config()).map(|position| account_at(accounts, position)).unwrap_or_default(),
+                            fee_bps: fee_bps.with_prec(20).to_string(),
+                            ..Default::default()
+                        }
+                    }
+                    DBC_PROGRAM => {
+                        let Some(e) = self_cpis().find_map(|cpi| match dbc::decode_event(cpi.idl_version, &cpi.data) {
+                            Some(dbc::CurveEvent::InitializePool(e)) => Some(e),
+                            _ => None,
+                        }) else {
+                            continue;
+                        };
+                        let instruction = dbc::decode_instruction(inst.idl_version, &inst.data);
+                        PoolCreated {
+                            protocol: "dbc".to_string(),
+                            pool: bs58::encode(e.pool).into_string(),
+                            instruction: instruction.as_ref().map(|i| i.name().to_string()).unwrap_or_default(),
+                            token_a_mint: bs58::encode(e.base_mint).into_string(),
+                            token_b_mint: instruction.and_then(|i| i.quote_mint()).map(|position| account_at(accounts, position)).unwrap_or_default(),
+                            creator: bs58::encode(e.creator).into_string(),
+                            config: bs58::encode(e.config).into_string(),
+                            ..Default::default()
+                        }
+                    }
+                    _ => continue,
+                };
+
+                ordinal += 1;
+                pool.block_slot = slot;
+                pool.block_time = block_time;
+                pool.tx_id = tx_id.clone();
+                pool.ordinal = ordinal;
+                if pool.creator.is_empty() {
+                    pool.creator = fee_payer.clone();
+                }
+                pools.push(pool);
+            }
+        }
+    }
+
+    Ok(PoolsCreated { pools })
+}
+
+// Effective fee rate of every swap, over the input amount when fees are charged on the input token,
+// otherwise over the output amount before fees
+pub fn pool_fee_rates(swaps: PoolSwaps) -> PoolFeeRates {
+    let mut fee_rates = Vec::new();
+
+    for swap in swaps.swaps {
+        let fees = swap.lp_fee + swap.protocol_fee + swap.partner_fee + swap.referral_fee;
+        let (input_mint, input_amount, output_amount) = if swap.is_buy {
+            (&swap.quote_mint, swap.quote_amount, swap.base_amount)
+        } else {
+            (&swap.base_mint, swap.base_amount, swap.quote_amount)
+        };
+        let charged_on = if swap.fee_mint == *input_mint { input_amount } else { output_amount + fees };
+        if swap.fee_mint.is_empty() || charged_on == 0 {
+            continue;
+        }
+
+        let fee_rate = (BigDecimal::from(fees) / BigDecimal::from(charged_on)).with_prec(20);
+        fee_rates.push(PoolFeeRate {
+            block_slot: swap.block_slot,
+            block_time: swap.block_time,
+            tx_id: swap.tx_id,
+            ordinal: swap.ordinal,
+            protocol: swap.protocol,
+            pool: swap.pool,
+            fee_rate: fee_rate.to_string(),
+            active_bin_id: swap.active_bin_id,
+            bins_crossed: swap.bins_crossed,
+        });
+    }
+
+    PoolFeeRates { fee_rates }
+}
+
+// Why data whose decoding returned None did not decode, from the discriminators the decoder knows and those of
+// the IDL instructions it leaves undecoded
+fn decode_failure(data: &[u8], known: &[[u8; 8]], undecoded: &[(&str, [u8; 8])]) -> &'static str {
+    if data.len() < 8 {
+        "data_too_short"
+    } else if known.iter().any(|discriminator| discriminator[..] == data[..8]) {
+        "borsh_decode_failed"
+    } else if undecoded.iter().any(|(_, discriminator)| discriminator[..] == data[..8]) {
+        "not_decoded"
+    } else {
+        "unknown_discriminator"
+    }
+}
+
+// Meteora instructions and events the decoders could not decode, to spot program upgrades they do not handle yet
+pub fn decode_errors(block: &MeteoraInstructions) -> Result<DecodeErrors, substreams::errors::Error> {
+    let mut errors = Vec::new();
+    let mut ordinal = 0u64;
+
+    for trx in &block.transactions {
+        for inst in &trx.instructions {
+            let program_id = inst.program_id.as_str();
+            let (known_instructions, undecoded_instructions, known_events) = match program_id {
+                DYNAMIC_VAULT_PROGRAM => {
+                    (dynamic_vault::INSTRUCTIONS, dynamic_vault::UNDECODED_INSTRUCTIONS, dynamic_vault::EVENTS)
+                }
+                FARM_PROGRAM => (farm::INSTRUCTIONS, &[][..], &[][..]),
+                ZAP_PROGRAM => (zap::INSTRUCTIONS, &[][..], &[][..]),
+                DAMM_V1_PROGRAM => (damm_v1::INSTRUCTIONS, damm_v1::UNDECODED_INSTRUCTIONS, damm_v1::EVENTS),
+                DLMM_PROGRAM => (dlmm::INSTRUCTIONS, dlmm::UNDECODED_INSTRUCTIONS, dlmm::EVENTS),
+                DAMM_V2_PROGRAM => (damm_v2::INSTRUCTIONS, damm_v2::UNDECODED_INSTRUCTIONS, damm_v2::EVENTS),
+                DBC_PROGRAM => (dbc::INSTRUCTIONS, dbc::UNDECODED_INSTRUCTIONS, dbc::EVENTS),
+                _ => continue,
+            };
+
+            // (kind, data starting at the discriminator)
+            let mut failures: Vec<(&str, &[u8])> = Vec::new();
+            let self_cpi_event = match program_id {
+                _ if !inst.data.starts_with(&EVENT_IX_TAG) => None,
+                DLMM_PROGRAM => Some(dlmm::decode_event(inst.idl_version, &inst.data).is_some()),
+                DAMM_V2_PROGRAM => Some(damm_v2::decode_event(inst.idl_version, &inst.data).is_some()),
+                DBC_PROGRAM => Some(dbc::decode_event(inst.idl_version, &inst.data).is_some()),
+                _ => None,
+            };
+            match self_cpi_event {
+                Some(true) => {}
+                Some(false) => failures.push(("event", &inst.data[8..])),
+                None if inst.instruction_type.is_empty() => failures.push(("instruction", &inst.data)),
+                None => {}
+            }
+            for payload in &inst.log_events {
+                let decoded = match program_id {
+                    DYNAMIC_VAULT_PROGRAM => dynamic_vault::decode_event(inst.idl_version, payload).is_some(),
+                    DAMM_V1_PROGRAM => damm_v1::decode_event(inst.idl_version, payload).is_some(),
+                    _ => true,
+                };
+                if !decoded {
+                    failures.push(("event", payload));
+                }
+            }
+
+            for (kind, data) in failures {
+                let (known, undecoded) =
+                    if kind == "instruction" { (known_instructions, undecoded_instructions) } else { (known_events, &[][..]) };
+                let reason =
+                    if inst.idl_version == 0 { "unsupported_idl_version" } else { decode_failure(data, known, undecoded) };
+                // Decoders only implement the events the modules read, so other events are expected
+                if kind == "event" && reason == "unknown_discriminator" {
+                    continue;
+                }
+
+                ordinal += 1;
+                errors.push(DecodeError {
+                    block_slot: block.block_slot,
+                    tx_id: trx.tx_id.clone(),
+                    instruction_index: inst.instruction_index,
+                    inner_instruction_index: inst.inner_instruction_index,
+                    program_id: inst.program_id.clone(),
+                    idl_version: inst.idl_version,
+                    kind: kind.to_string(),
+                    discriminator: data.iter().take(8).map(|byte| format!("{:02x}", byte)).collect(),
+                    data_length: data.len() as u32,
+                    reason: reason.to_string(),
+                    ordinal,
+                });
+            }
+        }
+    }
+
+    Ok(DecodeErrors { errors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::instruction_data;
+
+    fn instruction(idl_version: u32, data: Vec<u8>) -> MeteoraInstruction {
+        MeteoraInstruction {
+            program_id: DLMM_PROGRAM.to_string(),
+            instruction_type: instruction_name(DLMM_PROGRAM, idl_version, &data).unwrap_or_default().to_string(),
+            idl_version,
+            data,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_unsupported_layouts_and_unknown_instructions_from_ordinal_one() {
+        let block = MeteoraInstructions {
+            block_slot: 300_000_000,
+            transactions: vec![MeteoraTransaction {
+                tx_id: "tx".to_string(),
+                instructions: vec![
+                    instruction(1, instruction_data("swap", (1u64, 0u64))),
+                    instruction(0, instruction_data("swap", (1u64, 0u64))),
+                    instruction(1, vec![7; 16]),
+                    instruction(1, instruction_data("initialize_bin_array", 0i64)),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let errors = decode_errors(&block).unwrap().errors;
+        let reported: Vec<_> = errors.iter().map(|error| (error.ordinal, error.idl_version, error.reason.as_str())).collect();
+        assert_eq!(
+            reported,
+            [(1, 0, "unsupported_idl_version"), (2, 1, "unknown_discriminator"), (3, 1, "not_decoded")]
+        );
+        assert_eq!(errors[0].discriminator, "f8c69e91e17587c8");
+    }
+
+    #[test]
+    fn skips_positions_whose_bin_range_overflows() {
+        let block = MeteoraInstructions {
+            transactions: vec![MeteoraTransaction {
+                instructions: vec![
+                    instruction(1, instruction_data("initialize_position", (i32::MAX, 2i32))),
+                    instruction(1, instruction_data("initialize_position", (-10i32, 70i32))),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let events = dlmm_position_events(&block).unwrap().events;
+        let ranges: Vec<_> = events.iter().map(|event| (event.lower_bin_id, event.upper_bin_id)).collect();
+        assert_eq!(ranges, [(-10, 59)]);
+    }
+
+    #[test]
+    fn program_keys_match_their_base58_ids() {
+        for (key, program_id) in &METEORA_PROGRAMS {
+            assert_eq!(bs58::encode(key).into_string(), *program_id);
+            assert_eq!(meteora_program(key), Some(*program_id));
+            assert_eq!(meteora_program_key(program_id), Some(key));
+        }
+        assert_eq!(meteora_program(&zap::JUPITER_PROGRAM_ID), None);
+    }
+
+    #[test]
+    fn labels_zap_instructions_by_discriminator() {
+        let block = MeteoraInstructions {
+            transactions: vec![MeteoraTransaction {
+                fee_payer: "payer".to_string(),
+                instructions: vec![MeteoraInstruction {
+                    program_id: ZAP_PROGRAM.to_string(),
+                    idl_version: 1,
+                    data: instruction_data("zap_out", ()),
+                    accounts: vec!["user_token".to_string(), DLMM_PROGRAM.to_string()],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let events = meteora_events(&block);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0.instruction_type, "zap_out");
+        assert_eq!(events[0].0.signer, "payer");
+    }
+}
@@ -0,0 +1,80 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+const LOG_TRUNCATED: &str = "Log truncated";
+
+// Program invocation found in the transaction logs, with the Anchor `emit!`
+// payloads ("Program data: <base64>") logged while it was the running program
+struct Invocation {
+    program_id: String,
+    data: Vec<Vec<u8>>,
+}
+
+// Anchor event payloads of a transaction, grouped per program invocation in
+// execution order. Instructions must be claimed in the same order (a compiled
+// instruction, then its inner instructions), each claim consuming the next
+// invocation of the same program.
+pub struct InvocationLogs {
+    invocations: Vec<Invocation>,
+    cursor: usize,
+}
+
+impl InvocationLogs {
+    pub fn parse(log_messages: &[String]) -> Self {
+        let mut invocations: Vec<Invocation> = Vec::new();
+        let mut stack: Vec<usize> = Vec::new();
+
+        for log in log_messages {
+            // Later invocations cannot be attributed once the runtime truncates logs
+            if log.starts_with(LOG_TRUNCATED) {
+                break;
+            }
+
+            if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+                if let (Some(current), Ok(payload)) = (stack.last(), STANDARD.decode(data.trim())) {
+                    invocations[*current].data.push(payload);
+                }
+                continue;
+            }
+
+            let Some(rest) = log.strip_prefix("Program ") else { continue };
+            let mut parts = rest.split_whitespace();
+            let (Some(program_id), Some(action)) = (parts.next(), parts.next()) else { continue };
+            // Skip "Program log:", "Program data:" and "Program return:" lines
+            if program_id.ends_with(':') {
+                continue;
+            }
+
+            match action {
+                "invoke" => {
+                    stack.push(invocations.len());
+                    invocations.push(Invocation {
+                        program_id: program_id.to_string(),
+                        data: Vec::new(),
+                    });
+                }
+                "success" | "failed" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+
+        InvocationLogs { invocations, cursor: 0 }
+    }
+
+    // Claim the next invocation of `program_id` and return the payloads it emitted
+    pub fn next_for(&mut self, program_id: &str) -> &[Vec<u8>] {
+        let Some(offset) = self.invocations[self.cursor..]
+            .iter()
+            .position(|invocation| invocation.program_id == program_id)
+        else {
+            return &[];
+        };
+
+        let index = self.cursor + offset;
+        self.cursor = index + 1;
+        &self.invocations[index].data
+    }
+}
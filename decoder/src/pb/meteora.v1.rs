@@ -0,0 +1,1230 @@
+// This file is @generated by prost-build.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Output {
+    #[prost(message, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<MeteoraEvent>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeteoraEvent {
+    #[prost(string, tag = "1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(uint64, tag = "3")]
+    pub block_slot: u64,
+    #[prost(string, tag = "4")]
+    pub tx_id: ::prost::alloc::string::String,
+    /// Instruction authority when decoded and signing, otherwise the fee payer.
+    #[prost(string, tag = "5")]
+    pub signer: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub accounts: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "9")]
+    pub instruction_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "10")]
+    pub is_inner_instruction: bool,
+    #[prost(uint32, tag = "11")]
+    pub instruction_index: u32,
+    #[prost(uint32, tag = "12")]
+    pub inner_instruction_index: u32,
+    #[prost(string, tag = "13")]
+    pub outer_program: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub inner_program: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "15")]
+    pub txn_fee: u64,
+    #[prost(int64, tag = "16")]
+    pub signer_sol_change: i64,
+    /// Token accounts referenced by the instruction, resolved to their owner
+    /// wallet and mint through the spl-initialized-account foundational store.
+    #[prost(message, repeated, tag = "17")]
+    pub token_accounts: ::prost::alloc::vec::Vec<TokenAccount>,
+    /// Account paying the transaction fee (first static account key).
+    #[prost(string, tag = "18")]
+    pub fee_payer: ::prost::alloc::string::String,
+    /// Every account signing the transaction, per `message.header.num_required_signatures`.
+    #[prost(string, repeated, tag = "19")]
+    pub signers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Instruction-level authority (the IDL `user`/`payer` account), resolved
+    /// through address lookup tables when needed. Empty for undecoded instructions.
+    #[prost(string, tag = "20")]
+    pub authority: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenAccount {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    /// Role of the account in the instruction, named after the program IDL
+    /// (e.g. `user_token`, `a_token_vault`).
+    #[prost(string, tag = "2")]
+    pub role: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub mint: ::prost::alloc::string::String,
+    /// False when the foundational store had no entry for the account.
+    #[prost(bool, tag = "5")]
+    pub resolved: bool,
+}
+/// Every Meteora program invocation of a block, decoded once for the downstream modules.
+/// Only transactions invoking a Meteora program are kept.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeteoraInstructions {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(message, repeated, tag = "3")]
+    pub transactions: ::prost::alloc::vec::Vec<MeteoraTransaction>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeteoraTransaction {
+    #[prost(string, tag = "1")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub fee_payer: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "3")]
+    pub signers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag = "4")]
+    pub fee: u64,
+    /// SOL change of the fee payer over the transaction, fee included
+    #[prost(int64, tag = "5")]
+    pub signer_sol_change: i64,
+    #[prost(message, repeated, tag = "6")]
+    pub token_balances: ::prost::alloc::vec::Vec<TokenAccountBalance>,
+    /// Meteora instructions in execution order, along with the Jupiter routes invoked by zaps
+    #[prost(message, repeated, tag = "7")]
+    pub instructions: ::prost::alloc::vec::Vec<MeteoraInstruction>,
+}
+/// Token balances recorded for a token account touched by the transaction
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenAccountBalance {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub decimals: u32,
+    #[prost(uint64, tag = "5")]
+    pub pre_amount: u64,
+    #[prost(uint64, tag = "6")]
+    pub post_amount: u64,
+    /// False when the transaction recorded no balance before (or after) for the account
+    #[prost(bool, tag = "7")]
+    pub has_pre: bool,
+    #[prost(bool, tag = "8")]
+    pub has_post: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MeteoraInstruction {
+    /// Compiled instruction this instruction belongs to
+    #[prost(uint32, tag = "1")]
+    pub instruction_index: u32,
+    /// Position among the inner instructions of the compiled instruction, 0 for the compiled instruction itself
+    #[prost(uint32, tag = "2")]
+    pub inner_instruction_index: u32,
+    #[prost(bool, tag = "3")]
+    pub is_inner_instruction: bool,
+    /// CPI depth of inner instructions, 0 when the block does not record it
+    #[prost(uint32, tag = "4")]
+    pub stack_height: u32,
+    #[prost(string, tag = "5")]
+    pub program_id: ::prost::alloc::string::String,
+    /// Program of the compiled instruction
+    #[prost(string, tag = "6")]
+    pub outer_program: ::prost::alloc::string::String,
+    /// Instruction name from the program decoder, empty when the instruction is not decoded
+    #[prost(string, tag = "7")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "8")]
+    pub accounts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(bytes = "vec", tag = "9")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Anchor `emit!` payloads logged while this invocation was running
+    #[prost(bytes = "vec", repeated, tag = "10")]
+    pub log_events: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// IDL layout of the program at the block slot, from `versions::PROGRAM_VERSIONS`; 0 for Jupiter routes,
+    /// token transfers and Meteora instructions from slots no implemented layout covers, which are left undecoded
+    #[prost(uint32, tag = "11")]
+    pub idl_version: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeErrors {
+    #[prost(message, repeated, tag = "1")]
+    pub errors: ::prost::alloc::vec::Vec<DecodeError>,
+}
+/// Meteora instruction or event the decoders could not decode, usually after a program upgrade
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeError {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(string, tag = "2")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub instruction_index: u32,
+    #[prost(uint32, tag = "4")]
+    pub inner_instruction_index: u32,
+    #[prost(string, tag = "5")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "6")]
+    pub idl_version: u32,
+    /// "instruction" or "event"
+    #[prost(string, tag = "7")]
+    pub kind: ::prost::alloc::string::String,
+    /// Anchor discriminator of the instruction or event, hex encoded
+    #[prost(string, tag = "8")]
+    pub discriminator: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "9")]
+    pub data_length: u32,
+    /// "unknown_discriminator", "borsh_decode_failed", "data_too_short", "not_decoded" for IDL instructions the
+    /// decoders leave undecoded, or "unsupported_idl_version" for instructions and events from slots no implemented
+    /// layout covers
+    #[prost(string, tag = "10")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "11")]
+    pub ordinal: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultStrategyEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<VaultStrategyEvent>,
+}
+/// Dynamic Vault strategy lifecycle and rebalancing instruction
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultStrategyEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub instruction_index: u32,
+    #[prost(uint32, tag = "5")]
+    pub inner_instruction_index: u32,
+    /// One of `initialize_strategy`, `add_strategy`, `remove_strategy`,
+    /// `remove_strategy2`, `deposit_strategy`, `withdraw_strategy`, `collect_dust`.
+    #[prost(string, tag = "6")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub vault: ::prost::alloc::string::String,
+    /// Empty for `collect_dust`, which acts on the vault reserve only.
+    #[prost(string, tag = "8")]
+    pub strategy: ::prost::alloc::string::String,
+    /// Liquidity moved into (`deposit_strategy`) or out of (`withdraw_strategy`)
+    /// the strategy, in vault token base units.
+    #[prost(uint64, tag = "9")]
+    pub amount: u64,
+    /// Lending protocol behind the strategy, only known for `initialize_strategy`.
+    #[prost(string, tag = "10")]
+    pub strategy_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub authority: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultSharePrices {
+    #[prost(message, repeated, tag = "1")]
+    pub prices: ::prost::alloc::vec::Vec<VaultSharePrice>,
+}
+/// LP virtual price of a Dynamic Vault, observed from the deposits and
+/// withdrawals of a block
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultSharePrice {
+    #[prost(string, tag = "1")]
+    pub vault: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "3")]
+    pub block_time: i64,
+    /// Vault tokens per LP token (decimal string), from the token/LP ratio of the
+    /// last `AddLiquidity` or `RemoveLiquidity` event of the block.
+    #[prost(string, tag = "4")]
+    pub virtual_price: ::prost::alloc::string::String,
+    /// Vault total amount from the last `TotalAmount` event of the block, 0 when
+    /// none was emitted.
+    #[prost(uint64, tag = "5")]
+    pub total_amount: u64,
+    /// LP supply implied by `total_amount / virtual_price` (decimal string),
+    /// empty when `total_amount` is unknown.
+    #[prost(string, tag = "6")]
+    pub lp_supply: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultApySnapshots {
+    #[prost(message, repeated, tag = "1")]
+    pub snapshots: ::prost::alloc::vec::Vec<VaultApySnapshot>,
+}
+/// Hourly or daily LP virtual price point of a Dynamic Vault with the yield
+/// realized since the close of the previous interval
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultApySnapshot {
+    #[prost(string, tag = "1")]
+    pub vault: ::prost::alloc::string::String,
+    /// `1h` or `1d`.
+    #[prost(string, tag = "2")]
+    pub interval: ::prost::alloc::string::String,
+    /// Unix timestamp of the start of the interval.
+    #[prost(int64, tag = "3")]
+    pub bucket_start: i64,
+    #[prost(uint64, tag = "4")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "5")]
+    pub block_time: i64,
+    #[prost(string, tag = "6")]
+    pub virtual_price: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "7")]
+    pub total_amount: u64,
+    #[prost(string, tag = "8")]
+    pub lp_supply: ::prost::alloc::string::String,
+    /// Close of the previous interval, empty when the vault had no activity then.
+    #[prost(string, tag = "9")]
+    pub previous_virtual_price: ::prost::alloc::string::String,
+    #[prost(int64, tag = "10")]
+    pub previous_block_time: i64,
+    /// Annualized yield between the previous close and this price, compounded
+    /// over the elapsed time. 0 without a previous close.
+    #[prost(double, tag = "11")]
+    pub realized_apy: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultFeeEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub total_amounts: ::prost::alloc::vec::Vec<VaultTotalAmount>,
+    #[prost(message, repeated, tag = "2")]
+    pub fees: ::prost::alloc::vec::Vec<VaultFeeEvent>,
+}
+/// Vault total amount reported by a `TotalAmount` event
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultTotalAmount {
+    #[prost(string, tag = "1")]
+    pub vault: ::prost::alloc::string::String,
+    /// Position of the vault invocation in the block, shared with `VaultFeeEvent`.
+    #[prost(uint64, tag = "2")]
+    pub ordinal: u64,
+    #[prost(uint64, tag = "3")]
+    pub total_amount: u64,
+}
+/// Performance fee and locked profit accounting of a Dynamic Vault rebalance
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultFeeEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub vault: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub strategy: ::prost::alloc::string::String,
+    /// `fee_vault` LP token account receiving the performance fee.
+    #[prost(string, tag = "8")]
+    pub fee_recipient: ::prost::alloc::string::String,
+    /// LP tokens minted to the fee recipient (`PerformanceFee` event).
+    #[prost(uint64, tag = "9")]
+    pub performance_fee_lp: u64,
+    /// Loss reported by the strategy (`ReportLoss` event).
+    #[prost(uint64, tag = "10")]
+    pub loss: u64,
+    /// Change of the vault total amount across the rebalance, in vault token
+    /// base units. Filled by `map_vault_fees`.
+    #[prost(int64, tag = "11")]
+    pub profit: i64,
+    /// Fee LP minted, valued at the latest LP virtual price of the vault, in vault
+    /// token base units. 0 until a virtual price is known.
+    #[prost(uint64, tag = "12")]
+    pub performance_fee: u64,
+    /// Profit added to the locked profit tracker, released to LPs as it degrades.
+    #[prost(uint64, tag = "13")]
+    pub locked_profit: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultFlows {
+    #[prost(message, repeated, tag = "1")]
+    pub flows: ::prost::alloc::vec::Vec<VaultFlow>,
+}
+/// Capital flows of a Dynamic Vault in a block. Amounts are decimal strings in
+/// vault token base units; `net_flow` and `strategy_profit` are signed.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultFlow {
+    #[prost(string, tag = "1")]
+    pub vault: ::prost::alloc::string::String,
+    /// Vault token mint, read from the token balances of the vault `token_vault`.
+    #[prost(string, tag = "2")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "4")]
+    pub block_time: i64,
+    #[prost(string, tag = "5")]
+    pub deposits: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub withdrawals: ::prost::alloc::string::String,
+    /// Profit (or loss, when negative) realized by strategy rebalances.
+    #[prost(string, tag = "7")]
+    pub strategy_profit: ::prost::alloc::string::String,
+    /// deposits - withdrawals + strategy_profit.
+    #[prost(string, tag = "8")]
+    pub net_flow: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "9")]
+    pub deposit_count: u32,
+    #[prost(uint32, tag = "10")]
+    pub withdrawal_count: u32,
+    #[prost(uint32, tag = "11")]
+    pub rebalance_count: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmPositionEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<FarmPositionEvent>,
+}
+/// Farm instruction acting on a wallet staking position. Amounts are raw token
+/// amounts; `staked_amount` is signed (negative on withdraw).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmPositionEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub farm: ::prost::alloc::string::String,
+    /// Per-wallet `user` stake account of the farm
+    #[prost(string, tag = "7")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub staking_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub staked_amount: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "11")]
+    pub reward_a_claimed: u64,
+    #[prost(uint64, tag = "12")]
+    pub reward_b_claimed: u64,
+    #[prost(string, tag = "13")]
+    pub reward_a_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub reward_b_mint: ::prost::alloc::string::String,
+    /// Farm staking vault balance after the instruction
+    #[prost(uint64, tag = "15")]
+    pub total_staked: u64,
+}
+/// Last reward settlement of a staking position. The farm settles pending rewards
+/// and checkpoints the user reward-per-token at every deposit, withdraw and claim.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmCheckpoint {
+    #[prost(string, tag = "1")]
+    pub farm: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "5")]
+    pub block_time: i64,
+    #[prost(string, tag = "6")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "7")]
+    pub total_staked: u64,
+    /// Farm reward-per-token at the settlement, decimal strings of raw reward per raw
+    /// staked token accrued since the start block
+    #[prost(string, tag = "8")]
+    pub reward_per_token_stored_a: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub reward_per_token_stored_b: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmEmissionEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<FarmEmissionEvent>,
+}
+/// Farm creation, reward funding and reward duration update
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmEmissionEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub farm: ::prost::alloc::string::String,
+    /// Farm authority, or the funder for `fund`
+    #[prost(string, tag = "7")]
+    pub authority: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub staking_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub reward_a_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub reward_b_mint: ::prost::alloc::string::String,
+    /// Set by `initialize_pool` and `update_reward_duration`, in seconds
+    #[prost(uint64, tag = "11")]
+    pub reward_duration: u64,
+    #[prost(uint64, tag = "12")]
+    pub amount_a: u64,
+    #[prost(uint64, tag = "13")]
+    pub amount_b: u64,
+    /// Farm staking vault balance after the instruction
+    #[prost(uint64, tag = "14")]
+    pub total_staked: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmEmissionSchedules {
+    #[prost(message, repeated, tag = "1")]
+    pub schedules: ::prost::alloc::vec::Vec<FarmEmissionSchedule>,
+}
+/// Reward emission period started by a `fund`. Rates are decimal strings of raw
+/// reward tokens per second; rewards left from a still running period are rolled
+/// into the new one, as the farm does.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmEmissionSchedule {
+    #[prost(string, tag = "1")]
+    pub farm: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "3")]
+    pub block_time: i64,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(uint64, tag = "5")]
+    pub reward_duration: u64,
+    #[prost(int64, tag = "6")]
+    pub period_start: i64,
+    #[prost(int64, tag = "7")]
+    pub period_end: i64,
+    #[prost(uint64, tag = "8")]
+    pub amount_a: u64,
+    #[prost(uint64, tag = "9")]
+    pub amount_b: u64,
+    #[prost(string, tag = "10")]
+    pub reward_a_rate: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub reward_b_rate: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub reward_a_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "13")]
+    pub reward_b_mint: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ZapEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<ZapEvent>,
+}
+/// One-click deposit or withdrawal through the Zap program, with the swap and
+/// liquidity CPIs it performed. Amounts are raw token amounts.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ZapEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub instruction_index: u32,
+    #[prost(uint32, tag = "5")]
+    pub inner_instruction_index: u32,
+    #[prost(string, tag = "6")]
+    pub instruction_type: ::prost::alloc::string::String,
+    /// "in" or "out"
+    #[prost(string, tag = "7")]
+    pub direction: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub user: ::prost::alloc::string::String,
+    /// Input of the first and output of the last swap linked to the zap, from the
+    /// token transfers of those swaps; empty when the zap did not swap
+    #[prost(string, tag = "9")]
+    pub input_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "10")]
+    pub input_amount: u64,
+    #[prost(string, tag = "11")]
+    pub output_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "12")]
+    pub output_amount: u64,
+    #[prost(message, repeated, tag = "13")]
+    pub swaps: ::prost::alloc::vec::Vec<ZapSwap>,
+    #[prost(message, repeated, tag = "14")]
+    pub liquidity: ::prost::alloc::vec::Vec<ZapLiquidity>,
+    /// DLMM or DAMM v2 position, or DAMM v1 LP token account, of the last liquidity CPI
+    #[prost(string, tag = "15")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(string, tag = "16")]
+    pub lp_mint: ::prost::alloc::string::String,
+    #[prost(int64, tag = "17")]
+    pub lp_amount: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ZapSwap {
+    /// "dlmm", "damm_v1", "damm_v2" or "jupiter"
+    #[prost(string, tag = "1")]
+    pub program: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub pool: ::prost::alloc::string::String,
+    /// First token transfer of the swap. Without recorded transfers, the swap argument
+    /// (maximum input for exact-out swaps), or 0 for Jupiter routes
+    #[prost(uint64, tag = "4")]
+    pub amount_in: u64,
+    #[prost(string, tag = "5")]
+    pub input_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub output_mint: ::prost::alloc::string::String,
+    /// Last token transfer of the swap in another mint than the input
+    #[prost(uint64, tag = "7")]
+    pub amount_out: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ZapLiquidity {
+    /// "dlmm", "damm_v1" or "damm_v2"
+    #[prost(string, tag = "1")]
+    pub program: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub amount_a: u64,
+    #[prost(uint64, tag = "6")]
+    pub amount_b: u64,
+    /// DAMM v2 liquidity delta, as a decimal string
+    #[prost(string, tag = "7")]
+    pub liquidity: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPositionEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<DlmmPositionEvent>,
+}
+/// DLMM instruction in the lifecycle of a position, from `initialize_position*`
+/// to `close_position`. Amounts are raw token amounts from the DLMM events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPositionEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub owner: ::prost::alloc::string::String,
+    /// Bin range, set when the position is initialized
+    #[prost(int32, tag = "9")]
+    pub lower_bin_id: i32,
+    #[prost(int32, tag = "10")]
+    pub upper_bin_id: i32,
+    #[prost(int32, tag = "11")]
+    pub active_bin_id: i32,
+    #[prost(uint64, tag = "12")]
+    pub amount_x_added: u64,
+    #[prost(uint64, tag = "13")]
+    pub amount_y_added: u64,
+    #[prost(uint64, tag = "14")]
+    pub amount_x_removed: u64,
+    #[prost(uint64, tag = "15")]
+    pub amount_y_removed: u64,
+    /// Liquidity share per bin requested by the instruction
+    #[prost(message, repeated, tag = "16")]
+    pub bins: ::prost::alloc::vec::Vec<DlmmBinLiquidity>,
+    /// Bin range and strategy of `add_liquidity_by_strategy*`
+    #[prost(int32, tag = "17")]
+    pub strategy_min_bin_id: i32,
+    #[prost(int32, tag = "18")]
+    pub strategy_max_bin_id: i32,
+    #[prost(uint32, tag = "19")]
+    pub strategy_type: u32,
+    #[prost(uint64, tag = "20")]
+    pub fee_x_claimed: u64,
+    #[prost(uint64, tag = "21")]
+    pub fee_y_claimed: u64,
+    #[prost(uint64, tag = "22")]
+    pub reward_index: u64,
+    #[prost(uint64, tag = "23")]
+    pub reward_claimed: u64,
+}
+/// Share of the liquidity added to or removed from a bin. Distributions and
+/// removals are basis points; weights are relative to the other bins.
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DlmmBinLiquidity {
+    #[prost(int32, tag = "1")]
+    pub bin_id: i32,
+    #[prost(uint32, tag = "2")]
+    pub distribution_x: u32,
+    #[prost(uint32, tag = "3")]
+    pub distribution_y: u32,
+    #[prost(uint32, tag = "4")]
+    pub weight: u32,
+    #[prost(uint32, tag = "5")]
+    pub bps_to_remove: u32,
+}
+/// Open DLMM position
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPosition {
+    #[prost(string, tag = "1")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(int32, tag = "4")]
+    pub lower_bin_id: i32,
+    #[prost(int32, tag = "5")]
+    pub upper_bin_id: i32,
+    #[prost(uint64, tag = "6")]
+    pub created_block_slot: u64,
+    #[prost(int64, tag = "7")]
+    pub created_block_time: i64,
+}
+/// DLMM swaps and pair creations, from the events DLMM emits through self-CPI
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmSwapEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub swaps: ::prost::alloc::vec::Vec<DlmmSwap>,
+    #[prost(message, repeated, tag = "2")]
+    pub pairs: ::prost::alloc::vec::Vec<DlmmPairCreated>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmSwap {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub token_x_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub token_y_mint: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "10")]
+    pub token_x_decimals: u32,
+    #[prost(uint32, tag = "11")]
+    pub token_y_decimals: u32,
+    #[prost(int32, tag = "12")]
+    pub start_bin_id: i32,
+    /// Active bin after the swap
+    #[prost(int32, tag = "13")]
+    pub end_bin_id: i32,
+    #[prost(uint64, tag = "14")]
+    pub amount_in: u64,
+    #[prost(uint64, tag = "15")]
+    pub amount_out: u64,
+    #[prost(bool, tag = "16")]
+    pub swap_for_y: bool,
+    #[prost(uint64, tag = "17")]
+    pub fee: u64,
+    #[prost(uint64, tag = "18")]
+    pub protocol_fee: u64,
+    #[prost(uint64, tag = "19")]
+    pub host_fee: u64,
+    /// Total fee rate, as a decimal string
+    #[prost(string, tag = "20")]
+    pub fee_bps: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPairCreated {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(uint64, tag = "3")]
+    pub ordinal: u64,
+    #[prost(string, tag = "4")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub bin_step: u32,
+    #[prost(string, tag = "6")]
+    pub token_x_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub token_y_mint: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmFeeParameterEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<DlmmFeeParameterEvent>,
+}
+/// DLMM fee parameters: preset creation, pair creation from a preset, and base
+/// fee updates of a pair. Parameters an instruction does not set are 0.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmFeeParameterEvent {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub instruction_type: ::prost::alloc::string::String,
+    /// Empty for `initialize_preset_parameter`
+    #[prost(string, tag = "6")]
+    pub lb_pair: ::prost::alloc::string::String,
+    /// Preset created, or the preset a pair is created from
+    #[prost(string, tag = "7")]
+    pub preset_parameter: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "8")]
+    pub bin_step: u32,
+    #[prost(uint32, tag = "9")]
+    pub base_factor: u32,
+    #[prost(uint32, tag = "10")]
+    pub variable_fee_control: u32,
+    #[prost(uint32, tag = "11")]
+    pub max_volatility_accumulator: u32,
+    #[prost(uint32, tag = "12")]
+    pub protocol_share: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmFeeRates {
+    #[prost(message, repeated, tag = "1")]
+    pub fee_rates: ::prost::alloc::vec::Vec<DlmmFeeRate>,
+}
+/// Fee rate of a DLMM swap from its `Swap` event, split into the base fee of the
+/// pair and the variable fee on top, with the volatility accumulator the variable
+/// fee implies. Rates are decimal fractions; the split is empty when the pair fee
+/// parameters are unknown.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmFeeRate {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "6")]
+    pub bin_step: u32,
+    #[prost(string, tag = "7")]
+    pub fee_rate: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub base_fee_rate: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub variable_fee_rate: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "10")]
+    pub volatility_accumulator: u32,
+    /// Active bin after the swap
+    #[prost(int32, tag = "11")]
+    pub active_bin_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPriceTicks {
+    #[prost(message, repeated, tag = "1")]
+    pub ticks: ::prost::alloc::vec::Vec<DlmmPriceTick>,
+}
+/// Active bin of a DLMM pair after a swap, and its price
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DlmmPriceTick {
+    #[prost(string, tag = "1")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "3")]
+    pub block_time: i64,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "6")]
+    pub active_bin_id: i32,
+    /// 0 when the pair was created before the start block and its bin step is unknown
+    #[prost(uint32, tag = "7")]
+    pub bin_step: u32,
+    #[prost(string, tag = "8")]
+    pub token_x_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub token_y_mint: ::prost::alloc::string::String,
+    /// Lamports of token Y per lamport of token X
+    #[prost(double, tag = "10")]
+    pub price_per_lamport: f64,
+    /// Token Y per token X, adjusted for decimals
+    #[prost(double, tag = "11")]
+    pub price: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolSwaps {
+    #[prost(message, repeated, tag = "1")]
+    pub swaps: ::prost::alloc::vec::Vec<PoolSwap>,
+}
+/// Swap on a DLMM, DAMM v1, DAMM v2 or Dynamic Bonding Curve pool, normalized to
+/// the pool base/quote tokens: DLMM token X/Y, DAMM token A/B, DBC base/quote.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolSwap {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    /// "dlmm", "damm_v1", "damm_v2" or "dbc"
+    #[prost(string, tag = "5")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub quote_mint: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "10")]
+    pub base_decimals: u32,
+    #[prost(uint32, tag = "11")]
+    pub quote_decimals: u32,
+    /// Raw amounts exchanged, fees included on the input side
+    #[prost(uint64, tag = "12")]
+    pub base_amount: u64,
+    #[prost(uint64, tag = "13")]
+    pub quote_amount: u64,
+    /// True when the user receives the base token
+    #[prost(bool, tag = "14")]
+    pub is_buy: bool,
+    /// Quote per base and volumes in token units, adjusted for decimals, as decimal strings
+    #[prost(string, tag = "15")]
+    pub price: ::prost::alloc::string::String,
+    #[prost(string, tag = "16")]
+    pub base_volume: ::prost::alloc::string::String,
+    #[prost(string, tag = "17")]
+    pub quote_volume: ::prost::alloc::string::String,
+    /// Empty when no USD price is known for either token
+    #[prost(string, tag = "18")]
+    pub volume_usd: ::prost::alloc::string::String,
+    /// Fee split, as raw amounts of fee_mint: LP (DAMM v1 trade fee, DLMM LP share), protocol,
+    /// partner (DAMM v2 partner, DBC partner and creator trading fee) and referral (host) fees
+    #[prost(string, tag = "19")]
+    pub fee_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "20")]
+    pub lp_fee: u64,
+    #[prost(uint64, tag = "21")]
+    pub protocol_fee: u64,
+    #[prost(uint64, tag = "22")]
+    pub partner_fee: u64,
+    #[prost(uint64, tag = "23")]
+    pub referral_fee: u64,
+    /// Owner of the referral or host fee token account, when a referral fee was paid
+    #[prost(string, tag = "24")]
+    pub referrer: ::prost::alloc::string::String,
+    /// DLMM active bin after the swap and number of bins the swap moved the active bin by
+    #[prost(int32, tag = "25")]
+    pub active_bin_id: i32,
+    #[prost(uint32, tag = "26")]
+    pub bins_crossed: u32,
+    /// Raw pool reserves at the end of the transaction, from the pool token vaults; 0 for DAMM v1,
+    /// whose tokens sit in shared Dynamic Vaults
+    #[prost(uint64, tag = "27")]
+    pub base_reserve: u64,
+    #[prost(uint64, tag = "28")]
+    pub quote_reserve: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolCandles {
+    #[prost(message, repeated, tag = "1")]
+    pub candles: ::prost::alloc::vec::Vec<PoolCandle>,
+}
+/// OHLCV candle of a pool; prices are quote per base and volumes token units, as decimal strings
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolCandle {
+    #[prost(string, tag = "1")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub protocol: ::prost::alloc::string::String,
+    /// "1m", "5m", "1h" or "1d"
+    #[prost(string, tag = "3")]
+    pub interval: ::prost::alloc::string::String,
+    #[prost(int64, tag = "4")]
+    pub bucket_start: i64,
+    #[prost(string, tag = "5")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub quote_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub open: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub high: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub low: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub close: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub base_volume: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub quote_volume: ::prost::alloc::string::String,
+    #[prost(string, tag = "13")]
+    pub volume_usd: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "14")]
+    pub trades: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolsCreated {
+    #[prost(message, repeated, tag = "1")]
+    pub pools: ::prost::alloc::vec::Vec<PoolCreated>,
+}
+/// Pool creation on DLMM, DAMM v1, DAMM v2 or the Dynamic Bonding Curve
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolCreated {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    /// "dlmm", "damm_v1", "damm_v2" or "dbc"
+    #[prost(string, tag = "5")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub pool: ::prost::alloc::string::String,
+    /// Initialize instruction, when decoded
+    #[prost(string, tag = "7")]
+    pub instruction: ::prost::alloc::string::String,
+    /// DLMM token X/Y, DAMM token A/B, DBC base/quote
+    #[prost(string, tag = "8")]
+    pub token_a_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub token_b_mint: ::prost::alloc::string::String,
+    /// Pool creator, or the transaction fee payer when the creation event does not name one
+    #[prost(string, tag = "10")]
+    pub creator: ::prost::alloc::string::String,
+    /// DLMM preset parameter, DAMM v2 or DBC config account; empty when none
+    #[prost(string, tag = "11")]
+    pub config: ::prost::alloc::string::String,
+    /// DLMM bin step, in basis points
+    #[prost(uint32, tag = "12")]
+    pub bin_step: u32,
+    /// DAMM v2 base fee in basis points, as a decimal string
+    #[prost(string, tag = "13")]
+    pub fee_bps: ::prost::alloc::string::String,
+    /// DAMM v1 LP mint and pool type
+    #[prost(string, tag = "14")]
+    pub lp_mint: ::prost::alloc::string::String,
+    #[prost(bool, tag = "15")]
+    pub permissioned: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolFeeRates {
+    #[prost(message, repeated, tag = "1")]
+    pub fee_rates: ::prost::alloc::vec::Vec<PoolFeeRate>,
+}
+/// Effective fee rate paid by a swap: total fees over the amount they were charged on, as a decimal string
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PoolFeeRate {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub ordinal: u64,
+    #[prost(string, tag = "5")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub fee_rate: ::prost::alloc::string::String,
+    /// DLMM only: active bin after the swap and bins crossed, which drive the volatility accumulator
+    #[prost(int32, tag = "8")]
+    pub active_bin_id: i32,
+    #[prost(uint32, tag = "9")]
+    pub bins_crossed: u32,
+}
+/// Flat event tables for substreams-sink-files Parquet output: one table per repeated field.
+/// Every row starts with the same block columns; amounts are raw token base units unless noted.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlatEvents {
+    #[prost(message, repeated, tag = "1")]
+    pub swaps: ::prost::alloc::vec::Vec<SwapRow>,
+    #[prost(message, repeated, tag = "2")]
+    pub liquidity_changes: ::prost::alloc::vec::Vec<LiquidityChangeRow>,
+    #[prost(message, repeated, tag = "3")]
+    pub vault_flows: ::prost::alloc::vec::Vec<VaultFlowRow>,
+    #[prost(message, repeated, tag = "4")]
+    pub farm_actions: ::prost::alloc::vec::Vec<FarmActionRow>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SwapRow {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub ordinal: u64,
+    #[prost(string, tag = "6")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub base_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub quote_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "11")]
+    pub base_amount: u64,
+    #[prost(uint64, tag = "12")]
+    pub quote_amount: u64,
+    #[prost(bool, tag = "13")]
+    pub is_buy: bool,
+    /// Quote per base, and volumes in token units
+    #[prost(double, tag = "14")]
+    pub price: f64,
+    #[prost(double, tag = "15")]
+    pub base_volume: f64,
+    #[prost(double, tag = "16")]
+    pub quote_volume: f64,
+    /// 0 when no USD price is known
+    #[prost(double, tag = "17")]
+    pub volume_usd: f64,
+    #[prost(string, tag = "18")]
+    pub fee_mint: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "19")]
+    pub lp_fee: u64,
+    #[prost(uint64, tag = "20")]
+    pub protocol_fee: u64,
+    #[prost(uint64, tag = "21")]
+    pub partner_fee: u64,
+    #[prost(uint64, tag = "22")]
+    pub referral_fee: u64,
+    #[prost(string, tag = "23")]
+    pub referrer: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LiquidityChangeRow {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub ordinal: u64,
+    #[prost(string, tag = "6")]
+    pub protocol: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub position: ::prost::alloc::string::String,
+    /// "add" or "remove"
+    #[prost(string, tag = "10")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "12")]
+    pub amount_a: u64,
+    #[prost(uint64, tag = "13")]
+    pub amount_b: u64,
+    #[prost(int32, tag = "14")]
+    pub active_bin_id: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VaultFlowRow {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub vault: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub mint: ::prost::alloc::string::String,
+    /// Decimal strings of raw token amounts, as block sums can exceed the int64 range
+    #[prost(string, tag = "6")]
+    pub deposits: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub withdrawals: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub strategy_profit: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub net_flow: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "10")]
+    pub deposit_count: u32,
+    #[prost(uint32, tag = "11")]
+    pub withdrawal_count: u32,
+    #[prost(uint32, tag = "12")]
+    pub rebalance_count: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FarmActionRow {
+    #[prost(uint64, tag = "1")]
+    pub block_slot: u64,
+    #[prost(int64, tag = "2")]
+    pub block_time: i64,
+    #[prost(string, tag = "3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub ordinal: u64,
+    #[prost(string, tag = "6")]
+    pub farm: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub user: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub action: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub staking_mint: ::prost::alloc::string::String,
+    /// Decimal string, negative on withdraw; a u64 amount does not always fit int64
+    #[prost(string, tag = "10")]
+    pub staked_amount: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "11")]
+    pub reward_a_claimed: u64,
+    #[prost(uint64, tag = "12")]
+    pub reward_b_claimed: u64,
+    #[prost(string, tag = "13")]
+    pub reward_a_mint: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub reward_b_mint: ::prost::alloc::string::String,
+}
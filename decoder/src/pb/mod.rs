@@ -0,0 +1,17 @@
+// @generated
+pub mod meteora {
+    // @@protoc_insertion_point(attribute:meteora.v1)
+    pub mod v1 {
+        include!("meteora.v1.rs");
+        // @@protoc_insertion_point(meteora.v1)
+    }
+}
+pub mod sol {
+    pub mod spl {
+        // @@protoc_insertion_point(attribute:sol.spl.v1)
+        pub mod v1 {
+            include!("sol.spl.v1.rs");
+            // @@protoc_insertion_point(sol.spl.v1)
+        }
+    }
+}
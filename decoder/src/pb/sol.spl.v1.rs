@@ -0,0 +1,12 @@
+// This file is @generated by prost-build.
+/// Value stored by the spl-initialized-account foundational store, keyed by
+/// the raw 32 bytes of the token account address.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitializedAccount {
+    #[prost(bytes = "vec", tag = "1")]
+    pub account: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub mint: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub owner: ::prost::alloc::vec::Vec<u8>,
+}
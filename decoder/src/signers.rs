@@ -0,0 +1,36 @@
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+// Fee payer and signers of a transaction. Signers are the first
+// `num_required_signatures` static account keys of the message; they are never
+// loaded from an address lookup table, and the fee payer is always the first one.
+pub struct TransactionSigners {
+    pub fee_payer: String,
+    pub signers: Vec<String>,
+}
+
+pub fn transaction_signers(trx: &ConfirmedTransaction) -> TransactionSigners {
+    let Some(message) = trx.transaction.as_ref().and_then(|t| t.message.as_ref()) else {
+        return TransactionSigners {
+            fee_payer: String::new(),
+            signers: Vec::new(),
+        };
+    };
+
+    let num_required_signatures = message
+        .header
+        .as_ref()
+        .map(|header| header.num_required_signatures as usize)
+        .unwrap_or(1);
+
+    let signers: Vec<String> = message
+        .account_keys
+        .iter()
+        .take(num_required_signatures)
+        .map(|key| bs58::encode(key).into_string())
+        .collect();
+
+    TransactionSigners {
+        fee_payer: signers.first().cloned().unwrap_or_default(),
+        signers,
+    }
+}
@@ -0,0 +1,103 @@
+// Helpers for the decoder unit tests
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5, 0xd807aa98, 0x12835b01,
+    0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc,
+    0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147,
+    0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116, 0x1e376c08,
+    0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208,
+    0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-256, kept here so the tests need no hashing dependency
+fn sha256(message: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] =
+        [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u64) * 8).to_be_bytes());
+
+    for block in padded.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (bytes, word) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+// Anchor discriminator: sha256("{namespace}:{name}")[..8], with namespace `global` for instructions and `event`
+// for events
+pub(crate) fn discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    sha256(format!("{}:{}", namespace, name).as_bytes())[..8].try_into().unwrap()
+}
+
+// Payload of an event with the given Borsh fields, as logged by `emit!` after "Program data:"
+pub(crate) fn event_payload(name: &str, fields: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator("event", name).to_vec();
+    data.extend(borsh::to_vec(&fields).unwrap());
+    data
+}
+
+// Data of an `emit_cpi!` self-invocation carrying an event with the given Borsh fields
+pub(crate) fn event_data(name: &str, fields: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = crate::EVENT_IX_TAG.to_vec();
+    data.extend(event_payload(name, fields));
+    data
+}
+
+// Data of an instruction with the given Borsh arguments
+pub(crate) fn instruction_data(name: &str, args: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator("global", name).to_vec();
+    data.extend(borsh::to_vec(&args).unwrap());
+    data
+}
+
+// Every discriminator of a module decodes, with zeroed arguments, to the instruction whose name hashes to it
+pub(crate) fn assert_instruction_names<T>(
+    discriminators: &[[u8; 8]],
+    decode: fn(&[u8]) -> Option<T>,
+    name: fn(&T) -> &'static str,
+) {
+    for expected in discriminators {
+        let mut data = expected.to_vec();
+        data.extend_from_slice(&[0; 512]);
+        let instruction = decode(&data).unwrap_or_else(|| panic!("{:?} does not decode", expected));
+        assert_eq!(&discriminator("global", name(&instruction)), expected, "{}", name(&instruction));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::pb::meteora::v1::{MeteoraInstruction, MeteoraTransaction, TokenAccount, TokenAccountBalance};
+use crate::pb::sol::spl::v1::InitializedAccount;
+use std::collections::HashMap;
+use substreams_solana::b58;
+use substreams_solana::pb::sf::solana::r#type::v1::{ConfirmedTransaction, TokenBalance};
+
+// SPL Token programs, whose transfers are kept under zaps and the Jupiter routes they link
+pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const TOKEN_PROGRAM_ID: [u8; 32] = b58!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+const TOKEN_2022_PROGRAM_ID: [u8; 32] = b58!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// SPL Token instruction tags
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+
+// Raw token amount moved between two token accounts by an SPL Token `transfer` or `transfer_checked`
+pub struct TokenTransfer<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    // Only carried by `transfer_checked`
+    pub mint: Option<&'a str>,
+    pub amount: u64,
+}
+
+// Token program of an SPL Token `transfer` or `transfer_checked` invocation
+pub fn transfer_program(program_id: &[u8], data: &[u8]) -> Option<&'static str> {
+    if !matches!(data.first(), Some(&TRANSFER | &TRANSFER_CHECKED)) {
+        return None;
+    }
+    if program_id == TOKEN_PROGRAM_ID {
+        Some(TOKEN_PROGRAM)
+    } else if program_id == TOKEN_2022_PROGRAM_ID {
+        Some(TOKEN_2022_PROGRAM)
+    } else {
+        None
+    }
+}
+
+// Name of an SPL Token transfer instruction
+pub fn transfer_name(data: &[u8]) -> Option<&'static str> {
+    match *data.first()? {
+        TRANSFER => Some("transfer"),
+        TRANSFER_CHECKED => Some("transfer_checked"),
+        _ => None,
+    }
+}
+
+// Decode a kept SPL Token transfer
+pub fn transfer(inst: &MeteoraInstruction) -> Option<TokenTransfer<'_>> {
+    if inst.program_id != TOKEN_PROGRAM && inst.program_id != TOKEN_2022_PROGRAM {
+        return None;
+    }
+    let amount = u64::from_le_bytes(inst.data.get(1..9)?.try_into().ok()?);
+    let account = |position: usize| inst.accounts.get(position).map(String::as_str);
+
+    match *inst.data.first()? {
+        // source, destination, authority
+        TRANSFER => Some(TokenTransfer { source: account(0)?, destination: account(1)?, mint: None, amount }),
+        // source, mint, destination, authority
+        TRANSFER_CHECKED => Some(TokenTransfer { source: account(0)?, destination: account(2)?, mint: account(1), amount }),
+        _ => None,
+    }
+}
+
+// Token account referenced by a decoded instruction, waiting for owner resolution
+pub struct PendingTokenAccount {
+    pub address: Vec<u8>,
+    pub role: &'static str,
+}
+
+// Collect the token accounts of an instruction from its IDL account positions
+pub fn collect(instruction_accounts: &[String], layout: &'static [(usize, &'static str)]) -> Vec<PendingTokenAccount> {
+    layout
+        .iter()
+        .filter_map(|(position, role)| {
+            let address = bs58::decode(instruction_accounts.get(*position)?).into_vec().ok()?;
+            Some(PendingTokenAccount { address, role })
+        })
+        .collect()
+}
+
+// Attach the resolved owner and mint to a pending token account
+pub fn resolve(pending: &PendingTokenAccount, owners: &HashMap<Vec<u8>, InitializedAccount>) -> TokenAccount {
+    let mut token_account = TokenAccount {
+        address: bs58::encode(&pending.address).into_string(),
+        role: pending.role.to_string(),
+        ..Default::default()
+    };
+
+    if let Some(account) = owners.get(&pending.address) {
+        token_account.owner = bs58::encode(&account.owner).into_string();
+        token_account.mint = bs58::encode(&account.mint).into_string();
+        token_account.resolved = true;
+    }
+
+    token_account
+}
+
+fn raw_amount(balance: &TokenBalance) -> u64 {
+    balance
+        .ui_token_amount
+        .as_ref()
+        .and_then(|amount| amount.amount.parse().ok())
+        .unwrap_or_default()
+}
+
+// Token balances recorded by the transaction, keyed by token account address. Accounts are resolved
+// against static keys then lookup-table loaded addresses, in pre balance order then post-only accounts.
+pub fn transaction_balances(trx: &ConfirmedTransaction) -> Vec<TokenAccountBalance> {
+    let (Some(meta), Some(message)) = (trx.meta.as_ref(), trx.transaction.as_ref().and_then(|t| t.message.as_ref())) else {
+        return Vec::new();
+    };
+    let resolved: Vec<&Vec<u8>> = message
+        .account_keys
+        .iter()
+        .chain(meta.loaded_writable_addresses.iter())
+        .chain(meta.loaded_readonly_addresses.iter())
+        .collect();
+
+    let mut balances: Vec<(u32, TokenAccountBalance)> = Vec::new();
+    let pre = meta.pre_token_balances.iter().map(|balance| (balance, false));
+    let post = meta.post_token_balances.iter().map(|balance| (balance, true));
+    for (balance, is_post) in pre.chain(post) {
+        let index = match balances.iter().position(|(account_index, _)| *account_index == balance.account_index) {
+            Some(index) => index,
+            None => {
+                let Some(address) = resolved.get(balance.account_index as usize) else { continue };
+                balances.push((
+                    balance.account_index,
+                    TokenAccountBalance {
+                        address: bs58::encode(address).into_string(),
+                        ..Default::default()
+                    },
+                ));
+                balances.len() - 1
+            }
+        };
+
+        // Mint, owner and decimals are taken after the transaction when recorded, before it otherwise
+        let entry = &mut balances[index].1;
+        if is_post || !entry.has_post {
+            entry.mint = balance.mint.clone();
+            entry.owner = balance.owner.clone();
+            entry.decimals = balance.ui_token_amount.as_ref().map(|amount| amount.decimals).unwrap_or_default();
+        }
+        if is_post {
+            entry.post_amount = raw_amount(balance);
+            entry.has_post = true;
+        } else {
+            entry.pre_amount = raw_amount(balance);
+            entry.has_pre = true;
+        }
+    }
+
+    balances.into_iter().map(|(_, balance)| balance).collect()
+}
+
+fn token_balance<'a>(trx: &'a MeteoraTransaction, address: &str) -> Option<&'a TokenAccountBalance> {
+    trx.token_balances.iter().find(|balance| balance.address == address)
+}
+
+// Mint of a token account touched by the transaction, from its recorded token balances
+pub fn mint_from_balances(trx: &MeteoraTransaction, address: &str) -> Option<String> {
+    token_balance(trx, address).map(|balance| balance.mint.clone())
+}
+
+// Decimals of the mint of a token account touched by the transaction
+pub fn decimals_from_balances(trx: &MeteoraTransaction, address: &str) -> Option<u32> {
+    token_balance(trx, address).map(|balance| balance.decimals)
+}
+
+// Owner of a token account touched by the transaction, from its recorded token balances
+pub fn owner_from_balances(trx: &MeteoraTransaction, address: &str) -> Option<String> {
+    token_balance(trx, address)
+        .map(|balance| balance.owner.clone())
+        .filter(|owner| !owner.is_empty())
+}
+
+// Raw token amount held by a token account at the start of the transaction
+pub fn pre_balance(trx: &MeteoraTransaction, address: &str) -> Option<u64> {
+    token_balance(trx, address).map(|balance| balance.pre_amount)
+}
+
+// Raw token amount held by a token account at the end of the transaction
+pub fn post_balance(trx: &MeteoraTransaction, address: &str) -> Option<u64> {
+    token_balance(trx, address)
+        .filter(|balance| balance.has_post)
+        .map(|balance| balance.post_amount)
+}
+
+// Signed raw token amount change of a token account over the whole transaction
+pub fn balance_change(trx: &MeteoraTransaction, address: &str) -> Option<i128> {
+    token_balance(trx, address).map(|balance| balance.post_amount as i128 - balance.pre_amount as i128)
+}
@@ -0,0 +1,79 @@
+use crate::{
+    DAMM_V1_PROGRAM, DAMM_V2_PROGRAM, DBC_PROGRAM, DLMM_PROGRAM, DYNAMIC_VAULT_PROGRAM, FARM_PROGRAM, ZAP_PROGRAM,
+};
+
+// IDL layout of a program from `first_slot` until the next entry of the same program. Programs are upgraded in
+// place, so instructions and events are decoded with the layout that was live at the slot they executed in.
+pub struct ProgramVersion {
+    pub program_id: &'static str,
+    pub first_slot: u64,
+    // None for layouts the decoders do not implement, whose instructions are skipped instead of misparsed
+    pub idl_version: Option<u32>,
+}
+
+// Registered layouts, ordered by program then first slot. When an upgrade changes instruction or event layouts,
+// add an entry at the upgrade slot with the next version (or close the previous range with `idl_version: None`)
+// and dispatch on `MeteoraInstruction.idl_version` where the layouts differ.
+pub const PROGRAM_VERSIONS: &[ProgramVersion] = &[
+    ProgramVersion { program_id: DYNAMIC_VAULT_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: FARM_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: ZAP_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: DAMM_V1_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: DLMM_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: DAMM_V2_PROGRAM, first_slot: 0, idl_version: Some(1) },
+    ProgramVersion { program_id: DBC_PROGRAM, first_slot: 0, idl_version: Some(1) },
+];
+
+// Layout version of a program at a slot, None when no implemented layout covers it
+pub fn idl_version(program_id: &str, slot: u64) -> Option<u32> {
+    version_at(PROGRAM_VERSIONS, program_id, slot)
+}
+
+fn version_at(versions: &[ProgramVersion], program_id: &str, slot: u64) -> Option<u32> {
+    versions
+        .iter()
+        .rev()
+        .find(|version| version.program_id == program_id && version.first_slot <= slot)
+        .and_then(|version| version.idl_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERSIONS: &[ProgramVersion] = &[
+        ProgramVersion { program_id: DAMM_V1_PROGRAM, first_slot: 100, idl_version: Some(1) },
+        ProgramVersion { program_id: DLMM_PROGRAM, first_slot: 0, idl_version: Some(1) },
+        ProgramVersion { program_id: DLMM_PROGRAM, first_slot: 200, idl_version: Some(2) },
+        ProgramVersion { program_id: DLMM_PROGRAM, first_slot: 300, idl_version: None },
+    ];
+
+    #[test]
+    fn ranges_start_at_their_first_slot() {
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, 0), Some(1));
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, 199), Some(1));
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, 200), Some(2));
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, 299), Some(2));
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, 300), None);
+        assert_eq!(version_at(VERSIONS, DLMM_PROGRAM, u64::MAX), None);
+    }
+
+    #[test]
+    fn slots_before_the_first_range_are_unsupported() {
+        assert_eq!(version_at(VERSIONS, DAMM_V1_PROGRAM, 99), None);
+        assert_eq!(version_at(VERSIONS, DAMM_V1_PROGRAM, 100), Some(1));
+        assert_eq!(version_at(VERSIONS, DAMM_V2_PROGRAM, 100), None);
+    }
+
+    #[test]
+    fn registry_is_ordered_and_covers_every_program() {
+        for pair in PROGRAM_VERSIONS.windows(2) {
+            if pair[0].program_id == pair[1].program_id {
+                assert!(pair[0].first_slot < pair[1].first_slot, "{} ranges out of order", pair[0].program_id);
+            }
+        }
+        for (_, program_id) in crate::METEORA_PROGRAMS {
+            assert!(PROGRAM_VERSIONS.iter().any(|version| version.program_id == program_id), "{}", program_id);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use substreams_solana::b58;
+
+// Anchor instruction discriminators: sha256("global:<name>")[..8]
+const ZAP_OUT: [u8; 8] = [155, 108, 185, 112, 104, 210, 161, 64];
+const ZAP_IN_DAMM_V2: [u8; 8] = [243, 243, 119, 52, 199, 44, 154, 186];
+const ZAP_IN_DLMM_FOR_INITIALIZED_POSITION: [u8; 8] = [184, 71, 198, 231, 129, 110, 193, 67];
+const ZAP_IN_DLMM_FOR_UNINITIALIZED_POSITION: [u8; 8] = [59, 220, 182, 27, 254, 253, 2, 232];
+const INITIALIZE_TOKEN_LEDGER: [u8; 8] = [244, 63, 250, 192, 50, 44, 172, 250];
+const SET_TOKEN_LEDGER: [u8; 8] = [228, 85, 185, 112, 78, 79, 77, 2];
+const CLOSE_TOKEN_LEDGER: [u8; 8] = [242, 98, 207, 141, 212, 205, 199, 121];
+
+// Instruction discriminators known to the decoder
+pub const INSTRUCTIONS: &[[u8; 8]] = &[
+    ZAP_OUT,
+    ZAP_IN_DAMM_V2,
+    ZAP_IN_DLMM_FOR_INITIALIZED_POSITION,
+    ZAP_IN_DLMM_FOR_UNINITIALIZED_POSITION,
+    INITIALIZE_TOKEN_LEDGER,
+    SET_TOKEN_LEDGER,
+    CLOSE_TOKEN_LEDGER,
+];
+
+// Jupiter v6 aggregator, which zaps route their swaps through
+pub const JUPITER_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+pub const JUPITER_PROGRAM_ID: [u8; 32] = b58!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+const ROUTE: [u8; 8] = [229, 23, 203, 151, 122, 227, 173, 42];
+const SHARED_ACCOUNTS_ROUTE: [u8; 8] = [193, 32, 155, 51, 65, 214, 156, 129];
+const EXACT_OUT_ROUTE: [u8; 8] = [208, 51, 239, 151, 123, 43, 237, 92];
+const SHARED_ACCOUNTS_EXACT_OUT_ROUTE: [u8; 8] = [176, 209, 105, 168, 154, 125, 69, 62];
+const ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [150, 86, 71, 116, 167, 93, 14, 104];
+const SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER: [u8; 8] = [230, 121, 143, 80, 119, 159, 106, 170];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZapInstruction {
+    ZapOut,
+    ZapInDammV2,
+    ZapInDlmmForInitializedPosition,
+    ZapInDlmmForUninitializedPosition,
+    InitializeTokenLedger,
+    SetTokenLedger,
+    CloseTokenLedger,
+}
+
+impl ZapInstruction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ZapInstruction::ZapOut => "zap_out",
+            ZapInstruction::ZapInDammV2 => "zap_in_damm_v2",
+            ZapInstruction::ZapInDlmmForInitializedPosition => "zap_in_dlmm_for_initialized_position",
+            ZapInstruction::ZapInDlmmForUninitializedPosition => "zap_in_dlmm_for_uninitialized_position",
+            ZapInstruction::InitializeTokenLedger => "initialize_token_ledger",
+            ZapInstruction::SetTokenLedger => "set_token_ledger",
+            ZapInstruction::CloseTokenLedger => "close_token_ledger",
+        }
+    }
+
+    // "in" for one-click deposits, "out" for one-click withdrawals, None for token ledger bookkeeping
+    pub fn direction(&self) -> Option<&'static str> {
+        match self {
+            ZapInstruction::ZapOut => Some("out"),
+            ZapInstruction::ZapInDammV2
+            | ZapInstruction::ZapInDlmmForInitializedPosition
+            | ZapInstruction::ZapInDlmmForUninitializedPosition => Some("in"),
+            _ => None,
+        }
+    }
+}
+
+// Decode a Zap instruction with the layout of `idl_version`, None for layouts this decoder does not implement
+pub fn decode_instruction(idl_version: u32, data: &[u8]) -> Option<ZapInstruction> {
+    match idl_version {
+        1 => decode_instruction_v1(data),
+        _ => None,
+    }
+}
+
+// Version 1 layout: decode a Zap instruction from its Anchor discriminator
+fn decode_instruction_v1(data: &[u8]) -> Option<ZapInstruction> {
+    match data.get(..8)? {
+        d if d == ZAP_OUT => Some(ZapInstruction::ZapOut),
+        d if d == ZAP_IN_DAMM_V2 => Some(ZapInstruction::ZapInDammV2),
+        d if d == ZAP_IN_DLMM_FOR_INITIALIZED_POSITION => Some(ZapInstruction::ZapInDlmmForInitializedPosition),
+        d if d == ZAP_IN_DLMM_FOR_UNINITIALIZED_POSITION => Some(ZapInstruction::ZapInDlmmForUninitializedPosition),
+        d if d == INITIALIZE_TOKEN_LEDGER => Some(ZapInstruction::InitializeTokenLedger),
+        d if d == SET_TOKEN_LEDGER => Some(ZapInstruction::SetTokenLedger),
+        d if d == CLOSE_TOKEN_LEDGER => Some(ZapInstruction::CloseTokenLedger),
+        _ => None,
+    }
+}
+
+// Name of a Jupiter route instruction; route arguments are not decoded
+pub fn jupiter_route_name(data: &[u8]) -> Option<&'static str> {
+    match data.get(..8)? {
+        d if d == ROUTE => Some("route"),
+        d if d == SHARED_ACCOUNTS_ROUTE => Some("shared_accounts_route"),
+        d if d == EXACT_OUT_ROUTE => Some("exact_out_route"),
+        d if d == SHARED_ACCOUNTS_EXACT_OUT_ROUTE => Some("shared_accounts_exact_out_route"),
+        d if d == ROUTE_WITH_TOKEN_LEDGER => Some("route_with_token_ledger"),
+        d if d == SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER => Some("shared_accounts_route_with_token_ledger"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_instruction_names, discriminator};
+
+    #[test]
+    fn discriminators_match_their_names() {
+        assert_instruction_names(INSTRUCTIONS, decode_instruction_v1, ZapInstruction::name);
+        for route in [
+            ROUTE,
+            SHARED_ACCOUNTS_ROUTE,
+            EXACT_OUT_ROUTE,
+            SHARED_ACCOUNTS_EXACT_OUT_ROUTE,
+            ROUTE_WITH_TOKEN_LEDGER,
+            SHARED_ACCOUNTS_ROUTE_WITH_TOKEN_LEDGER,
+        ] {
+            let name = jupiter_route_name(&route).unwrap();
+            assert_eq!(discriminator("global", name), route, "{}", name);
+        }
+    }
+
+    #[test]
+    fn only_zaps_have_a_direction() {
+        let direction = |name: &str| decode_instruction(1, &discriminator("global", name)).unwrap().direction();
+        assert_eq!(direction("zap_out"), Some("out"));
+        assert_eq!(direction("zap_in_dlmm_for_uninitialized_position"), Some("in"));
+        assert_eq!(direction("set_token_ledger"), None);
+        assert!(decode_instruction(1, &ZAP_OUT[..7]).is_none());
+    }
+}
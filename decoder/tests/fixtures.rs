@@ -0,0 +1,853 @@
+// Decodes the recorded blocks in `tests/fixtures/*.binpb` once, runs the extraction behind each map module
+// over the decoded instructions and compares their output to `tests/fixtures/golden/{fixture}/{handler}.txt`.
+// Set UPDATE_GOLDEN=1 to rewrite the golden files after an intended change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use meteora_decoder as meteora;
+use prost::Message;
+use meteora::pb::meteora::v1::MeteoraInstructions;
+use substreams_solana::pb::sf::solana::r#type::v1::{
+    Block, CompiledInstruction, ConfirmedTransaction, InnerInstruction, InnerInstructions, MessageHeader,
+    TokenBalance, Transaction, TransactionStatusMeta, UiTokenAmount, UnixTimestamp,
+};
+
+type Handler = fn(&MeteoraInstructions) -> String;
+
+const HANDLERS: &[(&str, Handler)] = &[
+    ("map_meteora_instructions", |instructions| format!("{:#?}", instructions)),
+    // Events before the foundational store resolves their token account owners
+    ("map_spl_instructions", |instructions| {
+        let events: Vec<_> = meteora::meteora_events(instructions)
+            .into_iter()
+            .map(|(event, pending)| {
+                let token_accounts: Vec<_> = pending
+                    .iter()
+                    .map(|account| (bs58::encode(&account.address).into_string(), account.role))
+                    .collect();
+                (event, token_accounts)
+            })
+            .collect();
+        format!("{:#?}", events)
+    }),
+    ("map_vault_strategy_events", |instructions| format!("{:#?}", meteora::vault_strategy_events(instructions).unwrap())),
+    ("map_vault_share_prices", |instructions| format!("{:#?}", meteora::vault_share_prices(instructions).unwrap())),
+    ("map_vault_fee_events", |instructions| format!("{:#?}", meteora::vault_fee_events(instructions).unwrap())),
+    ("map_vault_analytics", |instructions| {
+        let fees = meteora::vault_fee_events(instructions).unwrap();
+        format!("{:#?}", meteora::vault_analytics(instructions, fees).unwrap())
+    }),
+    ("map_farm_position_events", |instructions| format!("{:#?}", meteora::farm_position_events(instructions).unwrap())),
+    ("map_farm_emission_events", |instructions| format!("{:#?}", meteora::farm_emission_events(instructions).unwrap())),
+    ("map_zap_events", |instructions| format!("{:#?}", meteora::zap_events(instructions).unwrap())),
+    ("map_dlmm_position_events", |instructions| format!("{:#?}", meteora::dlmm_position_events(instructions).unwrap())),
+    ("map_dlmm_swap_events", |instructions| format!("{:#?}", meteora::dlmm_swap_events(instructions).unwrap())),
+    ("map_dlmm_fee_parameter_events", |instructions| {
+        format!("{:#?}", meteora::dlmm_fee_parameter_events(instructions).unwrap())
+    }),
+    ("map_pool_swaps", |instructions| format!("{:#?}", meteora::pool_swaps(instructions).unwrap())),
+    ("map_pools_created", |instructions| format!("{:#?}", meteora::pools_created(instructions).unwrap())),
+    ("map_decode_errors", |instructions| format!("{:#?}", meteora::decode_errors(instructions).unwrap())),
+];
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[test]
+fn handlers_match_golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "binpb"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty(), "no fixture blocks in tests/fixtures");
+
+    let mut mismatches = Vec::new();
+    for fixture in fixtures {
+        let block = Block::decode(fs::read(&fixture).unwrap().as_slice()).unwrap();
+        let instructions = meteora::meteora_instructions(&block);
+        let golden_dir = fixtures_dir().join("golden").join(fixture.file_stem().unwrap());
+
+        for (name, handler) in HANDLERS {
+            let output = handler(&instructions);
+            let golden = golden_dir.join(format!("{}.txt", name));
+            if update {
+                fs::create_dir_all(&golden_dir).unwrap();
+                fs::write(&golden, &output).unwrap();
+                continue;
+            }
+            match fs::read_to_string(&golden) {
+                Ok(expected) if expected == output => {}
+                Ok(_) => mismatches.push(format!("{} differs from {}", name, golden.display())),
+                Err(_) => mismatches.push(format!("{} has no golden file {}", name, golden.display())),
+            }
+        }
+    }
+
+    assert!(mismatches.is_empty(), "rerun with UPDATE_GOLDEN=1 if intended:\n{}", mismatches.join("\n"));
+}
+
+// A top-level swap and a swap it reaches through CPI share instruction index 0 and inner index 0
+#[test]
+fn root_and_cpi_instructions_have_distinct_ids() {
+    let fixture = fixtures_dir().join("synthetic_dlmm_swap.binpb");
+    let mut block = Block::decode(fs::read(fixture).unwrap().as_slice()).unwrap();
+    let trx = &mut block.transactions[0];
+    let root = trx.transaction.as_ref().unwrap().message.as_ref().unwrap().instructions[0].clone();
+    trx.meta.as_mut().unwrap().inner_instructions[0].instructions.insert(
+        0,
+        InnerInstruction {
+            program_id_index: root.program_id_index,
+            accounts: root.accounts,
+            data: root.data,
+            stack_height: Some(2),
+        },
+    );
+
+    let events = meteora::meteora_events(&meteora::meteora_instructions(&block));
+    let ids: Vec<_> = events.iter().map(|(event, _)| meteora::instruction_id(event)).collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(
+        (events[0].0.inner_instruction_index, events[1].0.inner_instruction_index),
+        (0, 0)
+    );
+    assert_ne!(ids[0], ids[1]);
+}
+
+fn key(name: &str) -> Vec<u8> {
+    bs58::decode(name).into_vec().unwrap()
+}
+
+fn pubkey(name: &str) -> [u8; 32] {
+    key(name).try_into().unwrap()
+}
+
+fn token_balance(account_index: u32, mint: &str, owner: &str, amount: u64, decimals: u32) -> TokenBalance {
+    TokenBalance {
+        account_index,
+        mint: mint.to_string(),
+        owner: owner.to_string(),
+        ui_token_amount: Some(UiTokenAmount { decimals, amount: amount.to_string(), ..Default::default() }),
+        ..Default::default()
+    }
+}
+
+// Writes `synthetic_dlmm_swap.binpb`: one DLMM `swap` selling 2 token X for 300 USDC, with the `Swap`
+// event the pair emits through self-CPI and the reserve balances around it
+#[test]
+#[ignore]
+fn write_synthetic_dlmm_swap_fixture() {
+    const DLMM: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+    const MINT_X: &str = "So11111111111111111111111111111111111111112";
+    const MINT_Y: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let lb_pair = [2u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        lb_pair.to_vec(),
+        vec![3; 32], // reserve_x
+        vec![4; 32], // reserve_y
+        vec![5; 32], // user_token_in
+        vec![6; 32], // user_token_out
+        key(MINT_X),
+        key(MINT_Y),
+        vec![7; 32], // oracle
+        vec![8; 32], // event_authority
+        key("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+        key(DLMM),
+    ];
+    let (token_program, program) = (10u8, 11u8);
+
+    // swap: amount_in 2_000_000_000, min_amount_out 0
+    let mut swap_data = vec![248, 198, 158, 145, 225, 117, 135, 200];
+    swap_data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+    swap_data.extend_from_slice(&0u64.to_le_bytes());
+    // lb_pair, bin_array_bitmap_extension, reserve_x, reserve_y, user_token_in, user_token_out, token_x_mint,
+    // token_y_mint, oracle, host_fee_in, user, token_x_program, token_y_program, event_authority, program
+    let swap_accounts = vec![1, program, 2, 3, 4, 5, 6, 7, 8, program, 0, token_program, token_program, 9, program];
+
+    let mut event_data = vec![228, 69, 165, 46, 81, 203, 154, 29, 81, 108, 227, 190, 205, 208, 10, 196];
+    event_data.extend_from_slice(&lb_pair);
+    event_data.extend_from_slice(&user);
+    event_data.extend_from_slice(&(-120i32).to_le_bytes());
+    event_data.extend_from_slice(&(-123i32).to_le_bytes());
+    event_data.extend_from_slice(&2_000_000_000u64.to_le_bytes());
+    event_data.extend_from_slice(&300_000_000u64.to_le_bytes());
+    event_data.push(1); // swap_for_y
+    event_data.extend_from_slice(&5_000_000u64.to_le_bytes()); // fee
+    event_data.extend_from_slice(&250_000u64.to_le_bytes()); // protocol_fee
+    event_data.extend_from_slice(&25u128.to_le_bytes()); // fee_bps
+    event_data.extend_from_slice(&0u64.to_le_bytes()); // host_fee
+
+    let user_wallet = bs58::encode(user).into_string();
+    let pair = bs58::encode(lb_pair).into_string();
+    let trx = ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: vec![vec![9; 64]],
+            message: Some(substreams_solana::pb::sf::solana::r#type::v1::Message {
+                header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                account_keys,
+                instructions: vec![CompiledInstruction { program_id_index: program as u32, accounts: swap_accounts, data: swap_data }],
+                ..Default::default()
+            }),
+        }),
+        meta: Some(TransactionStatusMeta {
+            fee: 5_000,
+            inner_instructions: vec![InnerInstructions {
+                index: 0,
+                instructions: vec![InnerInstruction {
+                    program_id_index: program as u32,
+                    accounts: vec![9],
+                    data: event_data,
+                    stack_height: Some(2),
+                }],
+            }],
+            log_messages: vec![
+                format!("Program {} invoke [1]", DLMM),
+                "Program log: Instruction: Swap".to_string(),
+                format!("Program {} invoke [2]", DLMM),
+                format!("Program {} success", DLMM),
+                format!("Program {} success", DLMM),
+            ],
+            pre_token_balances: vec![
+                token_balance(2, MINT_X, &pair, 50_000_000_000, 9),
+                token_balance(3, MINT_Y, &pair, 9_000_000_000, 6),
+                token_balance(4, MINT_X, &user_wallet, 2_000_000_000, 9),
+                token_balance(5, MINT_Y, &user_wallet, 0, 6),
+            ],
+            post_token_balances: vec![
+                token_balance(2, MINT_X, &pair, 52_000_000_000, 9),
+                token_balance(3, MINT_Y, &pair, 8_700_000_000, 6),
+                token_balance(4, MINT_X, &user_wallet, 0, 9),
+                token_balance(5, MINT_Y, &user_wallet, 300_000_000, 6),
+            ],
+            ..Default::default()
+        }),
+    };
+
+    let block = Block {
+        slot: 300_000_000,
+        block_time: Some(UnixTimestamp { timestamp: 1_730_000_000 }),
+        transactions: vec![trx],
+        ..Default::default()
+    };
+    fs::write(fixtures_dir().join("synthetic_dlmm_swap.binpb"), block.encode_to_vec()).unwrap();
+}
+
+// Writes `synthetic_zap_in.binpb`: two zap-ins into the same DAMM v2 position in one transaction, each funded by a
+// top-level Jupiter route run between `set_token_ledger` and `zap_in_damm_v2`, with the token transfers of the
+// routes and of the `add_liquidity` CPIs
+#[test]
+#[ignore]
+fn write_synthetic_zap_in_fixture() {
+    const ZAP: &str = "zapvX9M3uf5pvy4wRPAbQgdQsM1xmuiFnkfHKPvwMiz";
+    const JUPITER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const DAMM_V2: &str = "cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const MINT_X: &str = "So11111111111111111111111111111111111111112";
+    const MINT_Y: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let route_pool = [2u8; 32];
+    let pool = [3u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        vec![4; 32], // token_ledger
+        vec![5; 32], // user_token_x
+        vec![6; 32], // user_token_y
+        vec![7; 32], // route_pool_x
+        vec![8; 32], // route_pool_y
+        pool.to_vec(),
+        vec![9; 32],  // position
+        vec![10; 32], // token_a_vault
+        vec![11; 32], // token_b_vault
+        key(MINT_X),
+        key(MINT_Y),
+        key(ZAP),
+        key(JUPITER),
+        key(DAMM_V2),
+        key(TOKEN),
+    ];
+    let (zap, jupiter, damm_v2, token_program) = (12u8, 13u8, 14u8, 15u8);
+
+    let transfer = |source: u8, destination: u8, authority: u8, amount: u64, stack_height: u32| {
+        let mut data = vec![3];
+        data.extend_from_slice(&amount.to_le_bytes());
+        InnerInstruction {
+            program_id_index: token_program as u32,
+            accounts: vec![source, destination, authority],
+            data,
+            stack_height: Some(stack_height),
+        }
+    };
+    // add_liquidity: liquidity_delta, token_a_amount_threshold, token_b_amount_threshold
+    let add_liquidity = |liquidity_delta: u128| {
+        let mut data = vec![181, 157, 89, 67, 143, 182, 52, 72];
+        data.extend_from_slice(&liquidity_delta.to_le_bytes());
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        data.extend_from_slice(&u64::MAX.to_le_bytes());
+        // pool, position, token_a_account, token_b_account, token_a_vault, token_b_vault, token_a_mint,
+        // token_b_mint, position_nft_account, owner, token_a_program, token_b_program, event_authority, program
+        InnerInstruction {
+            program_id_index: damm_v2 as u32,
+            accounts: vec![6, 7, 2, 3, 8, 9, 10, 11, 7, 0, token_program, token_program, 1, damm_v2],
+            data,
+            stack_height: Some(2),
+        }
+    };
+
+    // Each zap: the route sells `sold` of token X for `bought` of token Y, then the zap deposits `deposit_x` and
+    // everything bought
+    let zaps = [(1_000_000_000u64, 150_000_000u64, 1_000_000_000u64, 5_000u128), (500_000_000, 70_000_000, 400_000_000, 2_000)];
+    let mut instructions = Vec::new();
+    let mut inner_instructions = Vec::new();
+    let mut log_messages = Vec::new();
+    for (sold, bought, deposit_x, liquidity_delta) in zaps {
+        let ledger_index = instructions.len() as u32;
+        instructions.push(CompiledInstruction {
+            program_id_index: zap as u32,
+            accounts: vec![1, 3],
+            data: vec![228, 85, 185, 112, 78, 79, 77, 2],
+        });
+        // route_with_token_ledger, whose arguments are not decoded
+        instructions.push(CompiledInstruction {
+            program_id_index: jupiter as u32,
+            accounts: vec![token_program, 0, 2, 3, 1, 4, 5],
+            data: vec![150, 86, 71, 116, 167, 93, 14, 104],
+        });
+        inner_instructions.push(InnerInstructions {
+            index: ledger_index + 1,
+            instructions: vec![transfer(2, 4, 0, sold, 2), transfer(5, 3, 5, bought, 2)],
+        });
+        instructions.push(CompiledInstruction {
+            program_id_index: zap as u32,
+            accounts: vec![6, 7, 2, 3, 8, 9, 10, 11, 0, damm_v2, token_program],
+            data: vec![243, 243, 119, 52, 199, 44, 154, 186],
+        });
+        inner_instructions.push(InnerInstructions {
+            index: ledger_index + 2,
+            instructions: vec![
+                add_liquidity(liquidity_delta),
+                transfer(2, 8, 0, deposit_x, 3),
+                transfer(3, 9, 0, bought, 3),
+            ],
+        });
+        log_messages.extend([
+            format!("Program {} invoke [1]", ZAP),
+            "Program log: Instruction: SetTokenLedger".to_string(),
+            format!("Program {} success", ZAP),
+            format!("Program {} invoke [1]", JUPITER),
+            format!("Program {} success", JUPITER),
+            format!("Program {} invoke [1]", ZAP),
+            "Program log: Instruction: ZapInDammV2".to_string(),
+            format!("Program {} invoke [2]", DAMM_V2),
+            "Program log: Instruction: AddLiquidity".to_string(),
+            format!("Program {} success", DAMM_V2),
+            format!("Program {} success", ZAP),
+        ]);
+    }
+
+    let user_wallet = bs58::encode(user).into_string();
+    let route_owner = bs58::encode(route_pool).into_string();
+    let pool_owner = bs58::encode(pool).into_string();
+    let trx = ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: vec![vec![10; 64]],
+            message: Some(substreams_solana::pb::sf::solana::r#type::v1::Message {
+                header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                account_keys,
+                instructions,
+                ..Default::default()
+            }),
+        }),
+        meta: Some(TransactionStatusMeta {
+            fee: 5_000,
+            inner_instructions,
+            log_messages,
+            pre_token_balances: vec![
+                token_balance(2, MINT_X, &user_wallet, 3_000_000_000, 9),
+                token_balance(3, MINT_Y, &user_wallet, 0, 6),
+                token_balance(4, MINT_X, &route_owner, 10_000_000_000, 9),
+                token_balance(5, MINT_Y, &route_owner, 2_000_000_000, 6),
+                token_balance(8, MINT_X, &pool_owner, 40_000_000_000, 9),
+                token_balance(9, MINT_Y, &pool_owner, 6_000_000_000, 6),
+            ],
+            post_token_balances: vec![
+                token_balance(2, MINT_X, &user_wallet, 100_000_000, 9),
+                token_balance(3, MINT_Y, &user_wallet, 0, 6),
+                token_balance(4, MINT_X, &route_owner, 11_500_000_000, 9),
+                token_balance(5, MINT_Y, &route_owner, 1_780_000_000, 6),
+                token_balance(8, MINT_X, &pool_owner, 41_400_000_000, 9),
+                token_balance(9, MINT_Y, &pool_owner, 6_220_000_000, 6),
+            ],
+            ..Default::default()
+        }),
+    };
+
+    let block = Block {
+        slot: 300_000_100,
+        block_time: Some(UnixTimestamp { timestamp: 1_730_000_040 }),
+        transactions: vec![trx],
+        ..Default::default()
+    };
+    fs::write(fixtures_dir().join("synthetic_zap_in.binpb"), block.encode_to_vec()).unwrap();
+}
+
+// Instruction or event data: an 8 byte discriminator followed by Borsh arguments
+fn anchor_data(discriminator: [u8; 8], args: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend(borsh::to_vec(&args).unwrap());
+    data
+}
+
+// `emit_cpi!` self-invocation data carrying an event
+fn self_cpi_event(discriminator: [u8; 8], fields: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = vec![228, 69, 165, 46, 81, 203, 154, 29];
+    data.extend(anchor_data(discriminator, fields));
+    data
+}
+
+fn single_signer_transaction(
+    signature: u8,
+    account_keys: Vec<Vec<u8>>,
+    instructions: Vec<CompiledInstruction>,
+    meta: TransactionStatusMeta,
+) -> ConfirmedTransaction {
+    ConfirmedTransaction {
+        transaction: Some(Transaction {
+            signatures: vec![vec![signature; 64]],
+            message: Some(substreams_solana::pb::sf::solana::r#type::v1::Message {
+                header: Some(MessageHeader { num_required_signatures: 1, ..Default::default() }),
+                account_keys,
+                instructions,
+                ..Default::default()
+            }),
+        }),
+        meta: Some(TransactionStatusMeta { fee: 5_000, ..meta }),
+    }
+}
+
+fn write_block(name: &str, slot: u64, timestamp: i64, transactions: Vec<ConfirmedTransaction>) {
+    let block = Block {
+        slot,
+        block_time: Some(UnixTimestamp { timestamp }),
+        transactions,
+        ..Default::default()
+    };
+    fs::write(fixtures_dir().join(format!("{}.binpb", name)), block.encode_to_vec()).unwrap();
+}
+
+// Writes `synthetic_dlmm_pair_setup.binpb`: a DLMM preset created, a pair initialized from it, then a position
+// opened and funded on the pair, with the `LbPairCreate`, `PositionCreate` and `AddLiquidity` events
+#[test]
+#[ignore]
+fn write_synthetic_dlmm_pair_setup_fixture() {
+    const DLMM: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+    const MINT_X: &str = "So11111111111111111111111111111111111111112";
+    const MINT_Y: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let lb_pair = [2u8; 32];
+    let position = [3u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        lb_pair.to_vec(),
+        position.to_vec(),
+        vec![4; 32],  // preset_parameter
+        vec![5; 32],  // reserve_x
+        vec![6; 32],  // reserve_y
+        vec![7; 32],  // user_token_x
+        vec![8; 32],  // user_token_y
+        vec![9; 32],  // oracle
+        vec![10; 32], // bin_array_lower
+        vec![11; 32], // bin_array_upper
+        vec![12; 32], // event_authority
+        key(MINT_X),
+        key(MINT_Y),
+        key("11111111111111111111111111111111"),
+        key("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+        key(DLMM),
+    ];
+    let (preset, event_authority, mint_x, mint_y, system, token_program, program) = (3u8, 11u8, 12u8, 13u8, 14u8, 15u8, 16u8);
+
+    // initialize_preset_parameter: bin_step 10, base_factor 10_000, filter_period 30, decay_period 600,
+    // reduction_factor 5_000, variable_fee_control 40_000, max_volatility_accumulator 350_000, bin range, protocol_share
+    let preset_data = anchor_data(
+        [66, 188, 71, 211, 98, 109, 14, 186],
+        (10u16, 10_000u16, 30u16, 600u16, 5_000u16, 40_000u32, 350_000u32, -443_636i32, 443_636i32, 500u16),
+    );
+    // initialize_lb_pair: active_id -120, bin_step 10
+    let pair_data = anchor_data([45, 154, 237, 210, 221, 15, 166, 92], (-120i32, 10u16));
+    // initialize_position: lower_bin_id -123, width 7
+    let position_data = anchor_data([219, 192, 234, 71, 190, 191, 102, 80], (-123i32, 7i32));
+    // add_liquidity: 1 token X and 150 USDC spread over the three bins around the active one
+    let bins = vec![(-121i32, 0u16, 5_000u16), (-120, 5_000, 5_000), (-119, 5_000, 0)];
+    let liquidity_data = anchor_data([181, 157, 89, 67, 143, 182, 52, 72], (1_000_000_000u64, 150_000_000u64, bins));
+
+    let instructions = vec![
+        // preset_parameter, admin, system_program
+        CompiledInstruction { program_id_index: program as u32, accounts: vec![preset, 0, system], data: preset_data },
+        // lb_pair, bin_array_bitmap_extension, token_mint_x, token_mint_y, reserve_x, reserve_y, oracle,
+        // preset_parameter, funder, token_program, system_program, rent, event_authority, program
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![1, program, mint_x, mint_y, 4, 5, 8, preset, 0, token_program, system, system, event_authority, program],
+            data: pair_data,
+        },
+        // payer, position, lb_pair, owner, system_program, rent, event_authority, program
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![0, 2, 1, 0, system, system, event_authority, program],
+            data: position_data,
+        },
+        // position, lb_pair, bin_array_bitmap_extension, user_token_x, user_token_y, reserve_x, reserve_y,
+        // token_x_mint, token_y_mint, bin_array_lower, bin_array_upper, sender, token_x_program, token_y_program,
+        // event_authority, program
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![
+                2, 1, program, 6, 7, 4, 5, mint_x, mint_y, 9, 10, 0, token_program, token_program, event_authority, program,
+            ],
+            data: liquidity_data,
+        },
+    ];
+
+    let event = |index: u32, data: Vec<u8>| InnerInstructions {
+        index,
+        instructions: vec![InnerInstruction {
+            program_id_index: program as u32,
+            accounts: vec![event_authority],
+            data,
+            stack_height: Some(2),
+        }],
+    };
+    let inner_instructions = vec![
+        // LbPairCreate: lb_pair, bin_step, token_x, token_y
+        event(1, self_cpi_event([185, 74, 252, 125, 27, 215, 188, 111], (lb_pair, 10u16, pubkey(MINT_X), pubkey(MINT_Y)))),
+        // PositionCreate: lb_pair, position, owner
+        event(2, self_cpi_event([144, 142, 252, 84, 157, 53, 37, 121], (lb_pair, position, user))),
+        // AddLiquidity: lb_pair, from, position, amounts, active_bin_id
+        event(
+            3,
+            self_cpi_event(
+                [31, 94, 125, 90, 227, 52, 61, 186],
+                (lb_pair, user, position, [1_000_000_000u64, 150_000_000u64], -120i32),
+            ),
+        ),
+    ];
+
+    let mut log_messages = vec![
+        format!("Program {} invoke [1]", DLMM),
+        "Program log: Instruction: InitializePresetParameter".to_string(),
+        format!("Program {} success", DLMM),
+    ];
+    for name in ["InitializeLbPair", "InitializePosition", "AddLiquidity"] {
+        log_messages.extend([
+            format!("Program {} invoke [1]", DLMM),
+            format!("Program log: Instruction: {}", name),
+            format!("Program {} invoke [2]", DLMM),
+            format!("Program {} success", DLMM),
+            format!("Program {} success", DLMM),
+        ]);
+    }
+
+    let user_wallet = bs58::encode(user).into_string();
+    let pair = bs58::encode(lb_pair).into_string();
+    let meta = TransactionStatusMeta {
+        inner_instructions,
+        log_messages,
+        pre_token_balances: vec![
+            token_balance(6, MINT_X, &user_wallet, 5_000_000_000, 9),
+            token_balance(7, MINT_Y, &user_wallet, 500_000_000, 6),
+        ],
+        post_token_balances: vec![
+            token_balance(4, MINT_X, &pair, 1_000_000_000, 9),
+            token_balance(5, MINT_Y, &pair, 150_000_000, 6),
+            token_balance(6, MINT_X, &user_wallet, 4_000_000_000, 9),
+            token_balance(7, MINT_Y, &user_wallet, 350_000_000, 6),
+        ],
+        ..Default::default()
+    };
+    let trx = single_signer_transaction(11, account_keys, instructions, meta);
+    write_block("synthetic_dlmm_pair_setup", 300_000_200, 1_730_000_080, vec![trx]);
+}
+
+// Writes `synthetic_vault_deposit.binpb`: a Dynamic Vault `deposit` of 1_000 USDC minting 950 LP tokens, with the
+// `AddLiquidity` and `TotalAmount` events it logs and the reserve balances around it
+#[test]
+#[ignore]
+fn write_synthetic_vault_deposit_fixture() {
+    const VAULT: &str = "24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let vault = [2u8; 32];
+    let lp_mint = [3u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        vault.to_vec(),
+        vec![4; 32], // token_vault
+        lp_mint.to_vec(),
+        vec![5; 32], // user_token
+        vec![6; 32], // user_lp
+        key("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+        key(VAULT),
+    ];
+    let (token_program, program) = (6u8, 7u8);
+
+    // deposit: token_amount 1_000_000_000, minimum_lp_token_amount 0
+    let deposit_data = anchor_data([242, 35, 198, 137, 82, 225, 242, 182], (1_000_000_000u64, 0u64));
+    // vault, token_vault, lp_mint, user_token, user_lp, user, token_program
+    let instructions =
+        vec![CompiledInstruction { program_id_index: program as u32, accounts: vec![1, 2, 3, 4, 5, 0, token_program], data: deposit_data }];
+
+    let program_data = |data: Vec<u8>| format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(data));
+    let log_messages = vec![
+        format!("Program {} invoke [1]", VAULT),
+        "Program log: Instruction: Deposit".to_string(),
+        // AddLiquidity: lp_mint_amount, token_amount
+        program_data(anchor_data([31, 94, 125, 90, 227, 52, 61, 186], (950_000_000u64, 1_000_000_000u64))),
+        // TotalAmount: total_amount
+        program_data(anchor_data([92, 200, 122, 145, 211, 203, 49, 205], 21_000_000_000u64)),
+        format!("Program {} success", VAULT),
+    ];
+
+    let user_wallet = bs58::encode(user).into_string();
+    let vault_owner = bs58::encode(vault).into_string();
+    let lp = bs58::encode(lp_mint).into_string();
+    let meta = TransactionStatusMeta {
+        log_messages,
+        pre_token_balances: vec![
+            token_balance(2, USDC, &vault_owner, 20_000_000_000, 6),
+            token_balance(4, USDC, &user_wallet, 1_000_000_000, 6),
+            token_balance(5, &lp, &user_wallet, 0, 6),
+        ],
+        post_token_balances: vec![
+            token_balance(2, USDC, &vault_owner, 21_000_000_000, 6),
+            token_balance(4, USDC, &user_wallet, 0, 6),
+            token_balance(5, &lp, &user_wallet, 950_000_000, 6),
+        ],
+        ..Default::default()
+    };
+    let trx = single_signer_transaction(12, account_keys, instructions, meta);
+    write_block("synthetic_vault_deposit", 300_000_300, 1_730_000_120, vec![trx]);
+}
+
+// Writes `synthetic_farm_lifecycle.binpb`: a Farm created with a one day reward duration, a stake deposited into
+// it, then both rewards funded, in one transaction with the vault balances around it
+#[test]
+#[ignore]
+fn write_synthetic_farm_lifecycle_fixture() {
+    const FARM: &str = "FarmuwXPWXvefWUeqFAa5w6rifLkq5X6E8bimYvrhCB1";
+    const MINT_X: &str = "So11111111111111111111111111111111111111112";
+    const MINT_Y: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let farm = [2u8; 32];
+    let staking_mint = [3u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        farm.to_vec(),
+        staking_mint.to_vec(),
+        vec![4; 32], // staking_vault
+        vec![5; 32], // reward_a_vault
+        vec![6; 32], // reward_b_vault
+        vec![7; 32], // stake user account
+        vec![8; 32], // stake_from_account
+        vec![9; 32], // from_a
+        vec![10; 32], // from_b
+        key(MINT_X),
+        key(MINT_Y),
+        key("11111111111111111111111111111111"),
+        key("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+        key(FARM),
+    ];
+    let (mint_x, mint_y, system, token_program, program) = (10u8, 11u8, 12u8, 13u8, 14u8);
+
+    let instructions = vec![
+        // initialize_pool: reward_duration 86_400
+        // pool, staking_mint, staking_vault, reward_a_mint, reward_a_vault, reward_b_mint, reward_b_vault,
+        // authority, system_program, token_program, rent
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![1, 2, 3, mint_x, 4, mint_y, 5, 0, system, token_program, system],
+            data: anchor_data([95, 180, 10, 172, 84, 174, 232, 40], 86_400u64),
+        },
+        // deposit: amount 500_000_000
+        // pool, staking_vault, user, owner, stake_from_account, token_program
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![1, 3, 6, 0, 7, token_program],
+            data: anchor_data([242, 35, 198, 137, 82, 225, 242, 182], 500_000_000u64),
+        },
+        // fund: amount_a 8_640_000_000, amount_b 864_000_000
+        // pool, staking_vault, reward_a_vault, reward_b_vault, funder, from_a, from_b, token_program
+        CompiledInstruction {
+            program_id_index: program as u32,
+            accounts: vec![1, 3, 4, 5, 0, 8, 9, token_program],
+            data: anchor_data([218, 188, 111, 221, 152, 113, 174, 7], (8_640_000_000u64, 864_000_000u64)),
+        },
+    ];
+
+    let mut log_messages = Vec::new();
+    for name in ["InitializePool", "Deposit", "Fund"] {
+        log_messages.extend([
+            format!("Program {} invoke [1]", FARM),
+            format!("Program log: Instruction: {}", name),
+            format!("Program {} success", FARM),
+        ]);
+    }
+
+    let user_wallet = bs58::encode(user).into_string();
+    let farm_owner = bs58::encode(farm).into_string();
+    let staking = bs58::encode(staking_mint).into_string();
+    let meta = TransactionStatusMeta {
+        log_messages,
+        pre_token_balances: vec![
+            token_balance(7, &staking, &user_wallet, 500_000_000, 6),
+            token_balance(8, MINT_X, &user_wallet, 10_000_000_000, 9),
+            token_balance(9, MINT_Y, &user_wallet, 1_000_000_000, 6),
+        ],
+        post_token_balances: vec![
+            token_balance(3, &staking, &farm_owner, 500_000_000, 6),
+            token_balance(4, MINT_X, &farm_owner, 8_640_000_000, 9),
+            token_balance(5, MINT_Y, &farm_owner, 864_000_000, 6),
+            token_balance(7, &staking, &user_wallet, 0, 6),
+            token_balance(8, MINT_X, &user_wallet, 1_360_000_000, 9),
+            token_balance(9, MINT_Y, &user_wallet, 136_000_000, 6),
+        ],
+        ..Default::default()
+    };
+    let trx = single_signer_transaction(13, account_keys, instructions, meta);
+    write_block("synthetic_farm_lifecycle", 300_000_400, 1_730_000_160, vec![trx]);
+}
+
+// Writes `synthetic_damm_v1_dbc_swaps.binpb`: a DAMM v1 swap selling 1 SOL for USDC with the `Swap` event it logs,
+// and a Dynamic Bonding Curve swap buying base tokens for 5 USDC with its `EvtSwap` self-CPI event
+#[test]
+#[ignore]
+fn write_synthetic_damm_v1_dbc_swaps_fixture() {
+    const DAMM_V1: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB";
+    const DBC: &str = "dbcij3LWUppWqq96dh6gJWwBifmcGfLSB5D4DuSMaqN";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    const SOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    let user = [1u8; 32];
+    let user_wallet = bs58::encode(user).into_string();
+
+    // DAMM v1: pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault, b_token_vault,
+    // a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, protocol_token_fee, user, vault_program, token_program
+    let pool = [2u8; 32];
+    let mut account_keys: Vec<Vec<u8>> = vec![user.to_vec(), pool.to_vec()];
+    account_keys.extend((3u8..=12).map(|seed| vec![seed; 32]));
+    account_keys.extend([key(meteora::DYNAMIC_VAULT_PROGRAM), key(TOKEN), key(DAMM_V1)]);
+    let (vault_program, token_program, program) = (12u8, 13u8, 14u8);
+    // swap: in_amount 1_000_000_000, minimum_out_amount 0
+    let swap_data = anchor_data([248, 198, 158, 145, 225, 117, 135, 200], (1_000_000_000u64, 0u64));
+    let instructions = vec![CompiledInstruction {
+        program_id_index: program as u32,
+        accounts: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 0, 0, vault_program, token_program],
+        data: swap_data,
+    }];
+    let program_data = |data: Vec<u8>| format!("Program data: {}", base64::engine::general_purpose::STANDARD.encode(data));
+    // Swap: in_amount, out_amount, trade_fee, protocol_fee, host_fee
+    let swap_event = anchor_data([81, 108, 227, 190, 205, 208, 10, 196], (1_000_000_000u64, 149_000_000u64, 2_500_000u64, 500_000u64, 0u64));
+    let log_messages = vec![
+        format!("Program {} invoke [1]", DAMM_V1),
+        "Program log: Instruction: Swap".to_string(),
+        program_data(swap_event),
+        format!("Program {} success", DAMM_V1),
+    ];
+    let vault_a = bs58::encode([4u8; 32]).into_string();
+    let vault_b = bs58::encode([5u8; 32]).into_string();
+    let meta = TransactionStatusMeta {
+        log_messages,
+        pre_token_balances: vec![
+            token_balance(2, SOL, &user_wallet, 1_000_000_000, 9),
+            token_balance(3, USDC, &user_wallet, 0, 6),
+            token_balance(6, SOL, &vault_a, 80_000_000_000, 9),
+            token_balance(7, USDC, &vault_b, 12_000_000_000, 6),
+        ],
+        post_token_balances: vec![
+            token_balance(2, SOL, &user_wallet, 0, 9),
+            token_balance(3, USDC, &user_wallet, 149_000_000, 6),
+            token_balance(6, SOL, &vault_a, 81_000_000_000, 9),
+            token_balance(7, USDC, &vault_b, 11_851_000_000, 6),
+        ],
+        ..Default::default()
+    };
+    let damm_v1_swap = single_signer_transaction(14, account_keys, instructions, meta);
+
+    // DBC: pool_authority, config, pool, input_token_account, output_token_account, base_vault, quote_vault,
+    // base_mint, quote_mint, payer, token_base_program, token_quote_program, referral_token_account,
+    // event_authority, program
+    let curve_pool = [22u8; 32];
+    let config = [21u8; 32];
+    let base_mint = [27u8; 32];
+    let account_keys: Vec<Vec<u8>> = vec![
+        user.to_vec(),
+        vec![20; 32], // pool_authority
+        config.to_vec(),
+        curve_pool.to_vec(),
+        vec![23; 32], // input_token_account
+        vec![24; 32], // output_token_account
+        vec![25; 32], // base_vault
+        vec![26; 32], // quote_vault
+        base_mint.to_vec(),
+        key(USDC),
+        vec![28; 32], // event_authority
+        key(TOKEN),
+        key(DBC),
+    ];
+    let (event_authority, token_program, program) = (10u8, 11u8, 12u8);
+    // swap: amount_in 5_000_000, minimum_amount_out 0
+    let swap_data = anchor_data([248, 198, 158, 145, 225, 117, 135, 200], (5_000_000u64, 0u64));
+    let instructions = vec![CompiledInstruction {
+        program_id_index: program as u32,
+        accounts: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0, token_program, token_program, program, event_authority, program],
+        data: swap_data,
+    }];
+    // EvtSwap: pool, config, trade_direction (1: quote to base), has_referral, params, swap_result (actual input,
+    // output, next sqrt price, trading fee, protocol fee, referral fee), amount_in, current_timestamp
+    let swap_result = (4_950_000u64, 1_200_000_000_000u64, 1u128 << 64, 40_000u64, 10_000u64, 0u64);
+    let event = (curve_pool, config, 1u8, false, (5_000_000u64, 0u64), swap_result, 5_000_000u64, 1_730_000_200u64);
+    let inner_instructions = vec![InnerInstructions {
+        index: 0,
+        instructions: vec![InnerInstruction {
+            program_id_index: program as u32,
+            accounts: vec![event_authority],
+            data: self_cpi_event([27, 60, 21, 213, 138, 170, 187, 147], event),
+            stack_height: Some(2),
+        }],
+    }];
+    let log_messages = vec![
+        format!("Program {} invoke [1]", DBC),
+        "Program log: Instruction: Swap".to_string(),
+        format!("Program {} invoke [2]", DBC),
+        format!("Program {} success", DBC),
+        format!("Program {} success", DBC),
+    ];
+    let pool_authority = bs58::encode([20u8; 32]).into_string();
+    let base = bs58::encode(base_mint).into_string();
+    let meta = TransactionStatusMeta {
+        inner_instructions,
+        log_messages,
+        pre_token_balances: vec![
+            token_balance(4, USDC, &user_wallet, 5_000_000, 6),
+            token_balance(5, &base, &user_wallet, 0, 6),
+            token_balance(6, &base, &pool_authority, 800_000_000_000_000, 6),
+            token_balance(7, USDC, &pool_authority, 1_000_000_000, 6),
+        ],
+        post_token_balances: vec![
+            token_balance(4, USDC, &user_wallet, 0, 6),
+            token_balance(5, &base, &user_wallet, 1_200_000_000_000, 6),
+            token_balance(6, &base, &pool_authority, 798_800_000_000_000, 6),
+            token_balance(7, USDC, &pool_authority, 1_005_000_000, 6),
+        ],
+        ..Default::default()
+    };
+    let dbc_swap = single_signer_transaction(15, account_keys, instructions, meta);
+
+    write_block("synthetic_damm_v1_dbc_swaps", 300_000_500, 1_730_000_200, vec![damm_v1_swap, dbc_swap]);
+}
@@ -0,0 +1,135 @@
+-- ClickHouse schema for the `db_out` module (substreams-sink-sql). Upserted tables
+-- use ReplacingMergeTree on the row id; query them with FINAL for the latest row.
+-- ClickHouse has no partial updates, so `dlmm_positions` is sent as a full row when
+-- a position closes. Positions opened before the initial block of
+-- `store_dlmm_positions` lose their bin range and `created_block_slot` on close;
+-- join `dlmm_position_events` for them.
+
+CREATE TABLE IF NOT EXISTS instructions (
+    id String,
+    block_slot UInt64,
+    block_time Int64,
+    tx_id String,
+    instruction_index UInt32,
+    inner_instruction_index UInt32,
+    is_inner_instruction Bool,
+    program_id String,
+    instruction_type String,
+    signer String,
+    authority String,
+    fee_payer String,
+    txn_fee UInt64
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS pools (
+    id String,
+    pool String,
+    protocol String,
+    instruction String,
+    token_a_mint String,
+    token_b_mint String,
+    creator String,
+    config String,
+    bin_step UInt32,
+    fee_bps Nullable(Decimal(38, 10)),
+    lp_mint String,
+    permissioned Bool,
+    created_block_slot UInt64,
+    created_block_time Int64,
+    created_tx_id String
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS swaps (
+    id String,
+    block_slot UInt64,
+    block_time Int64,
+    tx_id String,
+    ordinal UInt64,
+    protocol LowCardinality(String),
+    pool String,
+    user String,
+    base_mint String,
+    quote_mint String,
+    base_amount UInt64,
+    quote_amount UInt64,
+    is_buy Bool,
+    price Nullable(Decimal(76, 20)),
+    volume_usd Nullable(Decimal(76, 20)),
+    fee_mint String,
+    lp_fee UInt64,
+    protocol_fee UInt64,
+    partner_fee UInt64,
+    referral_fee UInt64,
+    referrer String
+) ENGINE = ReplacingMergeTree() ORDER BY (pool, block_time, id);
+
+CREATE TABLE IF NOT EXISTS dlmm_positions (
+    id String,
+    position String,
+    lb_pair String,
+    owner String,
+    lower_bin_id Int32,
+    upper_bin_id Int32,
+    created_block_slot UInt64,
+    closed Bool,
+    closed_block_slot UInt64
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS dlmm_position_events (
+    id String,
+    block_slot UInt64,
+    block_time Int64,
+    tx_id String,
+    instruction_type String,
+    lb_pair String,
+    position String,
+    owner String,
+    amount_x_added UInt64,
+    amount_y_added UInt64,
+    amount_x_removed UInt64,
+    amount_y_removed UInt64,
+    fee_x_claimed UInt64,
+    fee_y_claimed UInt64,
+    reward_claimed UInt64
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS farm_position_events (
+    id String,
+    block_slot UInt64,
+    block_time Int64,
+    tx_id String,
+    instruction_type String,
+    farm String,
+    owner String,
+    staking_mint String,
+    staked_amount Int128,
+    reward_a_claimed UInt64,
+    reward_b_claimed UInt64
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS vault_flows (
+    id String,
+    vault String,
+    mint String,
+    block_slot UInt64,
+    block_time Int64,
+    deposits Int128,
+    withdrawals Int128,
+    strategy_profit Int128,
+    net_flow Int128
+) ENGINE = ReplacingMergeTree() ORDER BY id;
+
+CREATE TABLE IF NOT EXISTS pool_daily (
+    id String,
+    pool String,
+    day Int64,
+    protocol LowCardinality(String),
+    open Nullable(Decimal(76, 20)),
+    high Nullable(Decimal(76, 20)),
+    low Nullable(Decimal(76, 20)),
+    close Nullable(Decimal(76, 20)),
+    base_volume Nullable(Decimal(76, 20)),
+    quote_volume Nullable(Decimal(76, 20)),
+    volume_usd Nullable(Decimal(76, 20)),
+    trades UInt64
+) ENGINE = ReplacingMergeTree() ORDER BY id;
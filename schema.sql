@@ -0,0 +1,132 @@
+-- Postgres schema for the `db_out` module (substreams-sink-sql)
+
+CREATE TABLE IF NOT EXISTS instructions (
+    id TEXT NOT NULL PRIMARY KEY,
+    block_slot BIGINT NOT NULL,
+    block_time BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    instruction_index INTEGER NOT NULL,
+    inner_instruction_index INTEGER NOT NULL,
+    is_inner_instruction BOOLEAN NOT NULL,
+    program_id TEXT NOT NULL,
+    instruction_type TEXT,
+    signer TEXT,
+    authority TEXT,
+    fee_payer TEXT,
+    txn_fee NUMERIC
+);
+
+CREATE TABLE IF NOT EXISTS pools (
+    id TEXT NOT NULL PRIMARY KEY,
+    pool TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    instruction TEXT,
+    token_a_mint TEXT,
+    token_b_mint TEXT,
+    creator TEXT,
+    config TEXT,
+    bin_step INTEGER,
+    fee_bps NUMERIC,
+    lp_mint TEXT,
+    permissioned BOOLEAN,
+    created_block_slot BIGINT NOT NULL,
+    created_block_time BIGINT NOT NULL,
+    created_tx_id TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS swaps (
+    id TEXT NOT NULL PRIMARY KEY,
+    block_slot BIGINT NOT NULL,
+    block_time BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    ordinal BIGINT NOT NULL,
+    protocol TEXT NOT NULL,
+    pool TEXT NOT NULL,
+    "user" TEXT,
+    base_mint TEXT,
+    quote_mint TEXT,
+    base_amount NUMERIC NOT NULL,
+    quote_amount NUMERIC NOT NULL,
+    is_buy BOOLEAN NOT NULL,
+    price NUMERIC,
+    volume_usd NUMERIC,
+    fee_mint TEXT,
+    lp_fee NUMERIC,
+    protocol_fee NUMERIC,
+    partner_fee NUMERIC,
+    referral_fee NUMERIC,
+    referrer TEXT
+);
+CREATE INDEX IF NOT EXISTS swaps_pool_time ON swaps (pool, block_time);
+
+CREATE TABLE IF NOT EXISTS dlmm_positions (
+    id TEXT NOT NULL PRIMARY KEY,
+    position TEXT NOT NULL,
+    lb_pair TEXT,
+    owner TEXT,
+    lower_bin_id INTEGER,
+    upper_bin_id INTEGER,
+    created_block_slot BIGINT,
+    closed BOOLEAN NOT NULL,
+    closed_block_slot BIGINT
+);
+CREATE INDEX IF NOT EXISTS dlmm_positions_owner ON dlmm_positions (owner);
+
+CREATE TABLE IF NOT EXISTS dlmm_position_events (
+    id TEXT NOT NULL PRIMARY KEY,
+    block_slot BIGINT NOT NULL,
+    block_time BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    instruction_type TEXT NOT NULL,
+    lb_pair TEXT,
+    position TEXT NOT NULL,
+    owner TEXT,
+    amount_x_added NUMERIC,
+    amount_y_added NUMERIC,
+    amount_x_removed NUMERIC,
+    amount_y_removed NUMERIC,
+    fee_x_claimed NUMERIC,
+    fee_y_claimed NUMERIC,
+    reward_claimed NUMERIC
+);
+
+CREATE TABLE IF NOT EXISTS farm_position_events (
+    id TEXT NOT NULL PRIMARY KEY,
+    block_slot BIGINT NOT NULL,
+    block_time BIGINT NOT NULL,
+    tx_id TEXT NOT NULL,
+    instruction_type TEXT NOT NULL,
+    farm TEXT NOT NULL,
+    owner TEXT,
+    staking_mint TEXT,
+    staked_amount NUMERIC,
+    reward_a_claimed NUMERIC,
+    reward_b_claimed NUMERIC
+);
+
+CREATE TABLE IF NOT EXISTS vault_flows (
+    id TEXT NOT NULL PRIMARY KEY,
+    vault TEXT NOT NULL,
+    mint TEXT,
+    block_slot BIGINT NOT NULL,
+    block_time BIGINT NOT NULL,
+    deposits NUMERIC NOT NULL,
+    withdrawals NUMERIC NOT NULL,
+    strategy_profit NUMERIC NOT NULL,
+    net_flow NUMERIC NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS pool_daily (
+    id TEXT NOT NULL PRIMARY KEY,
+    pool TEXT NOT NULL,
+    day BIGINT NOT NULL,
+    protocol TEXT,
+    open NUMERIC,
+    high NUMERIC,
+    low NUMERIC,
+    close NUMERIC,
+    base_volume NUMERIC,
+    quote_volume NUMERIC,
+    volume_usd NUMERIC,
+    trades BIGINT
+);
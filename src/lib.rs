@@ -1,315 +1,2420 @@
+pub mod pb;
+
+use anyhow::Context;
+use meteora_decoder::{dlmm, token_accounts};
+use meteora_decoder::{
+    decode_errors, dlmm_fee_parameter_events, dlmm_position_events, dlmm_swap_events, farm_emission_events, farm_position_events, instruction_id,
+    meteora_instructions, pool_swaps, pools_created, vault_analytics,
+    vault_fee_events, vault_share_prices, vault_strategy_events, zap_events,
+};
+use pb::sol::spl::v1::InitializedAccount;
+use pb::sf::substreams::entity::v1::{entity_change, value, EntityChange, EntityChanges, Value};
+use pb::sf::substreams::sink::kv::v1::{kv_operation, KvOperation, KvOperations};
+use pb::sf::substreams::sink::database::v1::{table_change, DatabaseChanges, TableChange};
+use pb::meteora::v1::{
+    DecodeErrors, DlmmPosition, DlmmPositionEvents, DlmmPriceTick, DlmmPriceTicks, FarmActionRow, FlatEvents, LiquidityChangeRow, SwapRow, VaultFlowRow,
+    DlmmFeeParameterEvents, DlmmFeeRate, DlmmFeeRates, DlmmSwapEvents,
+    FarmCheckpoint, FarmEmissionEvents, FarmEmissionSchedule, FarmEmissionSchedules,
+    FarmPositionEvents, MeteoraEvent, MeteoraInstructions, Output, PoolCandle, PoolCandles, PoolCreated, PoolFeeRate, PoolFeeRates, PoolsCreated, PoolSwap, PoolSwaps, VaultApySnapshot, VaultApySnapshots, VaultFeeEvents, VaultSharePrice,
+    VaultFlows, VaultSharePrices, VaultStrategyEvents,
+    ZapEvents,
+};
+use substreams::pb::sf::substreams::foundational_store::v1::ResponseCode;
+use substreams::pb::substreams::store_delta::{self, Operation};
+use substreams::scalar::{BigDecimal, BigInt};
+use substreams::store::{
+    DeltaBigInt, DeltaProto, StoreGetBigDecimal, StoreMax, StoreMaxBigDecimal, StoreMaxInt64, StoreMin, StoreMinBigDecimal, StoreSetBigDecimal,
+    StoreSetIfNotExists, StoreSetIfNotExistsBigDecimal, StoreSetIfNotExistsProto, StoreAddBigDecimal, Deltas, FoundationalStore, StoreAdd, StoreAddBigInt, StoreAddInt64, StoreDelete, StoreGet, StoreGetBigInt, StoreGetInt64, StoreGetProto, StoreNew,
+    StoreSet, StoreSetBigInt, StoreSetInt64, StoreSetProto,
+};
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
-use substreams::store::FoundationalStore;
-use bs58;
-use std::collections::HashMap;
-
-// Program IDs for Meteora contracts
-const DYNAMIC_VAULT_PROGRAM: &str = "24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi";
-const FARM_PROGRAM: &str = "FarmuwXPWXvefWUeqFAa5w6rifKkq5X6E8bimYvrhCB1";
-const ZAP_PROGRAM: &str = "zapvX9M3uf5pvy4wRPAbQgdQsM1xmuiFnkfHKPvwMiz";
-const DAMM_V1_PROGRAM: &str = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB"; // Added DAMM v1
-
-// Meteora instruction discriminators (common patterns)
-const INSTRUCTION_INITIALIZE: u8 = 0;
-const INSTRUCTION_DEPOSIT: u8 = 1;
-const INSTRUCTION_WITHDRAW: u8 = 2;
-const INSTRUCTION_REBALANCE: u8 = 3;
-const INSTRUCTION_STAKE: u8 = 4;
-const INSTRUCTION_UNSTAKE: u8 = 5;
-const INSTRUCTION_CLAIM_REWARDS: u8 = 6;
-const INSTRUCTION_ZAP_IN: u8 = 7;
-const INSTRUCTION_ZAP_OUT: u8 = 8;
-
-// Significant event thresholds
-const SIGNIFICANT_AMOUNT_THRESHOLD: u64 = 10000; // $10k in lamports (adjust as needed)
-const SIGNIFICANT_USER_THRESHOLD: u32 = 100; // Minimum user count for aggregation
+use prost::Message;
+use std::collections::{HashMap, HashSet};
 
+// Shared decoding module: the block decoded once into the Meteora instructions every downstream module consumes
 #[substreams::handlers::map]
-pub fn map_block(block: Block) -> Result<Block, substreams::errors::Error> {
-    let mut meteora_transaction_count = 0;
-    let mut significant_events = 0;
-    let slot = block.slot;
-
-    for trx in block.clone().transactions_owned() {
-        if let Some(ref transaction) = trx.transaction {
-            let accounts = trx.resolved_accounts();
-
-            for inst in &transaction.message.as_ref().unwrap_or(&Default::default()).instructions {
-                let program_id = bs58::encode(&accounts[inst.program_id_index as usize]).into_string();
-
-                if is_meteora_program(&program_id) {
-                    meteora_transaction_count += 1;
-                    
-                    // Parse instruction type and check if significant
-                    if let Some(instruction_type) = parse_meteora_instruction(&inst.data) {
-                        if is_significant_event(&instruction_type, &inst.data) {
-                            significant_events += 1;
-                            
-                            // Extract amount if available
-                            if let Some(amount) = extract_amount_from_instruction(&inst.data) {
-                                substreams::log::info!(
-                                    "Significant Meteora event in slot {}: {} on program {} with amount {}", 
-                                    slot, instruction_type, program_id, amount
-                                );
-                            } else {
-                                substreams::log::info!(
-                                    "Significant Meteora event in slot {}: {} on program {}", 
-                                    slot, instruction_type, program_id
-                                );
-                            }
-                        } else {
-                            substreams::log::info!(
-                                "Meteora transaction in slot {}: {} on program {} (not significant)", 
-                                slot, instruction_type, program_id
-                            );
-                        }
-                    } else {
-                        substreams::log::info!("Found Meteora transaction in slot {}: program_id={} (unknown instruction)", slot, program_id);
-                    }
+pub fn map_meteora_instructions(block: Block) -> Result<MeteoraInstructions, substreams::errors::Error> {
+    let instructions = meteora_instructions(&block);
+
+    // Top-level instructions are only named once decoded; the others are reported by `map_decode_errors`
+    let top_level = instructions
+        .transactions
+        .iter()
+        .flat_map(|trx| trx.instructions.iter())
+        .filter(|inst| !inst.is_inner_instruction);
+    let (mut decoded, mut undecoded) = (0, 0);
+    for inst in top_level {
+        if inst.instruction_type.is_empty() {
+            undecoded += 1;
+        } else {
+            decoded += 1;
+            substreams::log::debug!("Meteora {} on program {} in slot {}", inst.instruction_type, inst.program_id, instructions.block_slot);
+        }
+    }
+
+    if !instructions.transactions.is_empty() {
+        substreams::log::info!(
+            "Block {} contains {} Meteora transactions, {} decoded and {} undecoded top-level instructions",
+            instructions.block_slot,
+            instructions.transactions.len(),
+            decoded,
+            undecoded
+        );
+    }
+
+    Ok(instructions)
+}
+
+// Look up every address in the spl-initialized-account foundational store in a
+// single batch and return the decoded owner/mint entries keyed by address
+pub fn lookup_owners(
+    account_owner_store: &FoundationalStore,
+    addresses: &[Vec<u8>],
+) -> HashMap<Vec<u8>, InitializedAccount> {
+    let mut owners = HashMap::new();
+    if addresses.is_empty() {
+        return owners;
+    }
+
+    let response = account_owner_store.get_all(addresses);
+    for entry in response.entries {
+        let Some(value) = entry
+            .response
+            .filter(|r| r.code == ResponseCode::Found as i32)
+            .and_then(|r| r.value)
+        else {
+            continue;
+        };
+
+        match InitializedAccount::decode(value.value.as_slice()) {
+            Ok(account) => {
+                owners.insert(entry.key, account);
+            }
+            Err(err) => {
+                substreams::log::info!(
+                    "Unable to decode foundational store entry for {}: {}",
+                    bs58::encode(&entry.key).into_string(),
+                    err
+                );
+            }
+        }
+    }
+
+    owners
+}
+
+// Enhanced foundational store module: decodes Meteora instructions and resolves every
+// token account they reference to its owner wallet and mint through the foundational store
+#[substreams::handlers::map]
+pub fn map_spl_instructions(
+    instructions: MeteoraInstructions,
+    account_owner_store: FoundationalStore,
+) -> Result<Output, substreams::errors::Error> {
+    let slot = instructions.block_slot;
+    let events = meteora_decoder::meteora_events(&instructions);
+
+    // Track user activity for analytics
+    let mut user_activity_map: HashMap<String, u32> = HashMap::new();
+    for (event, _) in &events {
+        *user_activity_map.entry(event.signer.clone()).or_insert(0) += 1;
+    }
+
+    // Resolve every referenced token account with a single foundational store batch
+    let mut seen = HashSet::new();
+    let addresses: Vec<Vec<u8>> = events
+        .iter()
+        .flat_map(|(_, pending)| pending.iter().map(|account| account.address.clone()))
+        .filter(|address| seen.insert(address.clone()))
+        .collect();
+    let owners = lookup_owners(&account_owner_store, &addresses);
+
+    let data: Vec<MeteoraEvent> = events
+        .into_iter()
+        .map(|(mut event, pending)| {
+            event.token_accounts = pending.iter().map(|account| token_accounts::resolve(account, &owners)).collect();
+            event
+        })
+        .collect();
+
+    if !data.is_empty() {
+        substreams::log::info!(
+            "Processed {} Meteora events with foundational store in slot {}, {}/{} token accounts resolved, {} active users",
+            data.len(), slot, owners.len(), addresses.len(), user_activity_map.len()
+        );
+
+        // Log power users (users with multiple transactions)
+        for (user, count) in &user_activity_map {
+            if *count > 1 {
+                substreams::log::info!("Power user {} has {} Meteora events in slot {}", user, count, slot);
+            }
+        }
+    }
+
+    Ok(Output { data })
+}
+
+#[substreams::handlers::map]
+pub fn map_vault_analytics(instructions: MeteoraInstructions, vault_fees: VaultFeeEvents) -> Result<VaultFlows, substreams::errors::Error> {
+    vault_analytics(&instructions, vault_fees)
+}
+
+fn vault_tvl_key(vault: &str, mint: &str) -> String {
+    format!("vault:{}:mint:{}", vault, mint)
+}
+
+// Running TVL of each vault in its token mint, in base units: every deposit, withdrawal and strategy profit is applied
+#[substreams::handlers::store]
+pub fn store_vault_tvl(flows: VaultFlows, store: StoreAddBigInt) {
+    add_vault_tvl(flows, &store);
+}
+
+fn add_vault_tvl<S: StoreAdd<BigInt>>(flows: VaultFlows, store: &S) {
+    for (ordinal, flow) in flows.flows.iter().enumerate() {
+        // Without the token vault balance in the block the mint is unknown, and the flow cannot be keyed
+        if flow.mint.is_empty() {
+            substreams::log::info!("Vault {} flow in slot {} has no resolved mint, TVL not updated", flow.vault, flow.block_slot);
+            continue;
+        }
+        let Ok(net_flow) = BigInt::try_from(&flow.net_flow) else {
+            substreams::log::info!("Vault {} flow in slot {} has invalid net flow {}", flow.vault, flow.block_slot, flow.net_flow);
+            continue;
+        };
+        store.add(ordinal as u64, vault_tvl_key(&flow.vault, &flow.mint), net_flow);
+    }
+}
+
+#[substreams::handlers::map]
+pub fn map_vault_strategy_events(instructions: MeteoraInstructions) -> Result<VaultStrategyEvents, substreams::errors::Error> {
+    vault_strategy_events(&instructions)
+}
+
+fn strategy_allocation_key(vault: &str, strategy: &str) -> String {
+    format!("vault:{}:strategy:{}", vault, strategy)
+}
+
+// Liquidity allocated by each vault to each of its strategies, in vault token base units
+#[substreams::handlers::store]
+pub fn store_strategy_allocations(events: VaultStrategyEvents, store: StoreAddBigInt) {
+    for (ordinal, event) in events.events.iter().enumerate() {
+        let key = strategy_allocation_key(&event.vault, &event.strategy);
+
+        match event.instruction_type.as_str() {
+            "deposit_strategy" => store.add(ordinal as u64, &key, BigInt::from(event.amount)),
+            "withdraw_strategy" => store.add(ordinal as u64, &key, BigInt::from(event.amount).neg()),
+            // Removing a strategy pulls all of its liquidity back into the vault
+            "remove_strategy" | "remove_strategy2" => store.delete_prefix(ordinal as i64, &key),
+            _ => {}
+        }
+    }
+}
+
+// Share price observation intervals, as (label, seconds)
+const SHARE_PRICE_INTERVALS: [(&str, i64); 2] = [("1h", 3_600), ("1d", 86_400)];
+const SECONDS_PER_YEAR: f64 = 31_536_000.0;
+
+#[substreams::handlers::map]
+pub fn map_vault_share_prices(instructions: MeteoraInstructions) -> Result<VaultSharePrices, substreams::errors::Error> {
+    vault_share_prices(&instructions)
+}
+
+fn share_price_key(vault: &str, interval: &str, bucket_start: i64) -> String {
+    format!("vault:{}:{}:{}", vault, interval, bucket_start)
+}
+
+fn latest_share_price_key(vault: &str) -> String {
+    format!("vault:{}:latest", vault)
+}
+
+// Last LP virtual price observed in each hourly and daily interval, and overall, per vault
+#[substreams::handlers::store]
+pub fn store_vault_share_prices(prices: VaultSharePrices, store: StoreSetProto<VaultSharePrice>) {
+    for (ordinal, price) in prices.prices.iter().enumerate() {
+        store.set(ordinal as u64, latest_share_price_key(&price.vault), price);
+        for (interval, seconds) in SHARE_PRICE_INTERVALS {
+            let bucket_start = price.block_time - price.block_time.rem_euclid(seconds);
+            store.set(ordinal as u64, share_price_key(&price.vault, interval, bucket_start), price);
+        }
+    }
+}
+
+// Hourly and daily LP virtual price series with the realized APY since the previous interval close
+#[substreams::handlers::map]
+pub fn map_vault_apy(
+    prices: VaultSharePrices,
+    share_price_store: StoreGetProto<VaultSharePrice>,
+) -> Result<VaultApySnapshots, substreams::errors::Error> {
+    let mut snapshots = Vec::new();
+
+    for price in prices.prices {
+        for (interval, seconds) in SHARE_PRICE_INTERVALS {
+            let bucket_start = price.block_time - price.block_time.rem_euclid(seconds);
+            let mut snapshot = VaultApySnapshot {
+                vault: price.vault.clone(),
+                interval: interval.to_string(),
+                bucket_start,
+                block_slot: price.block_slot,
+                block_time: price.block_time,
+                virtual_price: price.virtual_price.clone(),
+                total_amount: price.total_amount,
+                lp_supply: price.lp_supply.clone(),
+                ..Default::default()
+            };
+
+            let previous_key = share_price_key(&price.vault, interval, bucket_start - seconds);
+            if let Some(previous) = share_price_store.get_last(&previous_key) {
+                let current = parse_stored::<f64>(&price.virtual_price, "virtual price")?;
+                let before = parse_stored::<f64>(&previous.virtual_price, "previous virtual price")?;
+                let elapsed = (price.block_time - previous.block_time) as f64;
+                if before > 0.0 && elapsed > 0.0 {
+                    snapshot.realized_apy = (current / before).powf(SECONDS_PER_YEAR / elapsed) - 1.0;
+                }
+                snapshot.previous_virtual_price = previous.virtual_price;
+                snapshot.previous_block_time = previous.block_time;
+            }
+
+            snapshots.push(snapshot);
+        }
+    }
+
+    Ok(VaultApySnapshots { snapshots })
+}
+
+#[substreams::handlers::map]
+pub fn map_vault_fee_events(instructions: MeteoraInstructions) -> Result<VaultFeeEvents, substreams::errors::Error> {
+    vault_fee_events(&instructions)
+}
+
+// Latest total amount of each vault, keyed `vault:{vault}`, with one delta per `TotalAmount` event
+#[substreams::handlers::store]
+pub fn store_vault_total_amounts(events: VaultFeeEvents, store: StoreSetBigInt) {
+    for total in events.total_amounts {
+        store.set(total.ordinal, format!("vault:{}", total.vault), &BigInt::from(total.total_amount));
+    }
+}
+
+// Rebalance fee events with the realized profit, performance fee and locked profit derived from the total amount change.
+// The performance fee is the fee LP the vault minted, valued at the latest LP virtual price of the vault.
+#[substreams::handlers::map]
+pub fn map_vault_fees(
+    events: VaultFeeEvents,
+    total_amount_deltas: Deltas<DeltaBigInt>,
+    share_price_store: StoreGetProto<VaultSharePrice>,
+) -> Result<VaultFeeEvents, substreams::errors::Error> {
+    let mut fees = events.fees;
+
+    for fee in fees.iter_mut() {
+        let key = format!("vault:{}", fee.vault);
+        let Some(delta) = total_amount_deltas
+            .deltas
+            .iter()
+            .find(|delta| delta.ordinal == fee.ordinal && delta.key == key)
+        else {
+            continue;
+        };
+        // The first total amount seen for a vault has no previous value to compare against
+        if delta.operation != Operation::Update {
+            continue;
+        }
+
+        // Total amounts are u64 on chain, so their difference always fits an i128
+        let (Ok(new_total), Ok(old_total)) = (u64::try_from(&delta.new_value), u64::try_from(&delta.old_value)) else {
+            substreams::log::info!("Vault {} total amount out of u64 range in slot {}", fee.vault, fee.block_slot);
+            continue;
+        };
+        let change = new_total as i128 - old_total as i128;
+        let Ok(profit) = i64::try_from(change) else {
+            substreams::log::info!("Vault {} total amount change {} out of i64 range in slot {}", fee.vault, change, fee.block_slot);
+            continue;
+        };
+        fee.profit = profit;
+
+        if profit > 0 {
+            fee.locked_profit = profit as u64;
+            let virtual_price = share_price_store
+                .get_last(latest_share_price_key(&fee.vault))
+                .and_then(|price| BigDecimal::try_from(price.virtual_price.as_str()).ok());
+            match virtual_price {
+                Some(virtual_price) => {
+                    let value = (BigDecimal::from(fee.performance_fee_lp) * virtual_price).to_bigint();
+                    fee.performance_fee = u64::try_from(&value).unwrap_or(u64::MAX);
                 }
+                None if fee.performance_fee_lp > 0 => substreams::log::info!(
+                    "Vault {} has no LP virtual price yet, performance fee of {} LP left unvalued",
+                    fee.vault, fee.performance_fee_lp
+                ),
+                None => {}
             }
+        } else if profit < 0 {
+            // Loss: nothing is charged and no profit gets locked
+            substreams::log::info!(
+                "Vault {} total amount fell by {} in slot {} (reported loss {})",
+                fee.vault, -change, fee.block_slot, fee.loss
+            );
+        }
+
+        substreams::log::info!(
+            "Vault {} rebalance in slot {}: profit {}, performance fee {} ({} LP to {}), loss {}",
+            fee.vault, fee.block_slot, fee.profit, fee.performance_fee, fee.performance_fee_lp, fee.fee_recipient, fee.loss
+        );
+    }
+
+    Ok(VaultFeeEvents {
+        total_amounts: Vec::new(),
+        fees,
+    })
+}
+
+// Daily performance fee, locked profit and loss per vault, and fee LP per vault and fee recipient
+#[substreams::handlers::store]
+pub fn store_vault_fees(events: VaultFeeEvents, store: StoreAddBigInt) {
+    for fee in events.fees {
+        let day_start = fee.block_time - fee.block_time.rem_euclid(86_400);
+        let prefix = format!("vault:{}:1d:{}", fee.vault, day_start);
+
+        store.add(fee.ordinal, format!("{}:performance_fee", prefix), BigInt::from(fee.performance_fee));
+        store.add(fee.ordinal, format!("{}:performance_fee_lp", prefix), BigInt::from(fee.performance_fee_lp));
+        store.add(fee.ordinal, format!("{}:locked_profit", prefix), BigInt::from(fee.locked_profit));
+        store.add(fee.ordinal, format!("{}:loss", prefix), BigInt::from(fee.loss));
+        if !fee.fee_recipient.is_empty() {
+            store.add(
+                fee.ordinal,
+                format!("vault:{}:recipient:{}:1d:{}:performance_fee_lp", fee.vault, fee.fee_recipient, day_start),
+                BigInt::from(fee.performance_fee_lp),
+            );
         }
     }
+}
+
+#[substreams::handlers::map]
+pub fn map_farm_position_events(instructions: MeteoraInstructions) -> Result<FarmPositionEvents, substreams::errors::Error> {
+    farm_position_events(&instructions)
+}
+
+fn farm_position_key(farm: &str, owner: &str) -> String {
+    format!("farm:{}:owner:{}", farm, owner)
+}
+
+// Staked balance and cumulative rewards claimed per wallet and farm, keyed `farm:{farm}:owner:{owner}:{staked|reward_a_claimed|reward_b_claimed}`.
+// The staked balance is deleted when the stake account is closed
+#[substreams::handlers::store]
+pub fn store_farm_positions(events: FarmPositionEvents, store: StoreAddBigInt) {
+    add_farm_positions(events, &store);
+}
 
-    if meteora_transaction_count > 0 {
-        substreams::log::info!("Block {} contains {} Meteora transactions, {} significant events", slot, meteora_transaction_count, significant_events);
+fn add_farm_positions<S: StoreAdd<BigInt>>(events: FarmPositionEvents, store: &S) {
+    for event in events.events {
+        let prefix = farm_position_key(&event.farm, &event.owner);
+        if event.instruction_type == "close_user" {
+            store.delete_prefix(event.ordinal as i64, &format!("{}:staked", prefix));
+            continue;
+        }
+        let Ok(staked_amount) = BigInt::try_from(&event.staked_amount) else {
+            substreams::log::info!("Farm {} event {} has invalid staked amount {}", event.farm, event.tx_id, event.staked_amount);
+            continue;
+        };
+
+        store.add(event.ordinal, format!("{}:staked", prefix), staked_amount);
+        store.add(event.ordinal, format!("{}:reward_a_claimed", prefix), BigInt::from(event.reward_a_claimed));
+        store.add(event.ordinal, format!("{}:reward_b_claimed", prefix), BigInt::from(event.reward_b_claimed));
     }
+}
 
-    Ok(block)
+// Last reward settlement of each staking position, keyed `farm:{farm}:owner:{owner}`, deleted when the stake account is closed
+#[substreams::handlers::store]
+pub fn store_farm_checkpoints(
+    events: FarmPositionEvents,
+    reward_per_token_store: StoreGetBigDecimal,
+    store: StoreSetProto<FarmCheckpoint>,
+) {
+    for event in events.events {
+        let key = farm_position_key(&event.farm, &event.owner);
+        let reward_per_token = |reward: &str| {
+            reward_per_token_store
+                .get_at(event.ordinal, format!("farm:{}:reward_{}_per_token", event.farm, reward))
+                .unwrap_or_else(BigDecimal::zero)
+                .to_string()
+        };
+        match event.instruction_type.as_str() {
+            "deposit" | "withdraw" | "claim" => store.set(
+                event.ordinal,
+                key,
+                &FarmCheckpoint {
+                    reward_per_token_stored_a: reward_per_token("a"),
+                    reward_per_token_stored_b: reward_per_token("b"),
+                    farm: event.farm,
+                    owner: event.owner,
+                    user: event.user,
+                    block_slot: event.block_slot,
+                    block_time: event.block_time,
+                    instruction_type: event.instruction_type,
+                    total_staked: event.total_staked,
+                },
+            ),
+            "close_user" => store.delete_prefix(event.ordinal as i64, &key),
+            _ => {}
+        }
+    }
 }
 
-fn is_meteora_program(program_id: &String) -> bool {
-    program_id == DYNAMIC_VAULT_PROGRAM || program_id == FARM_PROGRAM || program_id == ZAP_PROGRAM || program_id == DAMM_V1_PROGRAM
+#[substreams::handlers::map]
+pub fn map_farm_emission_events(instructions: MeteoraInstructions) -> Result<FarmEmissionEvents, substreams::errors::Error> {
+    farm_emission_events(&instructions)
 }
 
-// Parse Meteora instruction type from instruction data
-fn parse_meteora_instruction(instruction_data: &[u8]) -> Option<String> {
-    if instruction_data.is_empty() {
-        return None;
+// Reward duration and last funding of each farm, keyed `farm:{farm}:{reward_duration|last_fund_time|last_fund_a|last_fund_b}`
+#[substreams::handlers::store]
+pub fn store_farm_emission_params(events: FarmEmissionEvents, store: StoreSetBigInt) {
+    for event in events.events {
+        let prefix = format!("farm:{}", event.farm);
+        match event.instruction_type.as_str() {
+            "initialize_pool" | "update_reward_duration" => {
+                store.set(event.ordinal, format!("{}:reward_duration", prefix), &BigInt::from(event.reward_duration));
+            }
+            "fund" => {
+                store.set(event.ordinal, format!("{}:last_fund_time", prefix), &BigInt::from(event.block_time));
+                store.set(event.ordinal, format!("{}:last_fund_a", prefix), &BigInt::from(event.amount_a));
+                store.set(event.ordinal, format!("{}:last_fund_b", prefix), &BigInt::from(event.amount_b));
+            }
+            _ => {}
+        }
     }
-    
-    match instruction_data[0] {
-        INSTRUCTION_INITIALIZE => Some("initialize".to_string()),
-        INSTRUCTION_DEPOSIT => Some("deposit".to_string()),
-        INSTRUCTION_WITHDRAW => Some("withdraw".to_string()),
-        INSTRUCTION_REBALANCE => Some("rebalance".to_string()),
-        INSTRUCTION_STAKE => Some("stake".to_string()),
-        INSTRUCTION_UNSTAKE => Some("unstake".to_string()),
-        INSTRUCTION_CLAIM_REWARDS => Some("claim_rewards".to_string()),
-        INSTRUCTION_ZAP_IN => Some("zap_in".to_string()),
-        INSTRUCTION_ZAP_OUT => Some("zap_out".to_string()),
-        _ => Some("unknown".to_string()),
+}
+
+// Reward emission period started by each `fund`. Rewards still undistributed from a running period are
+// rolled into the new rate, using the rate of the previous funding on its own.
+#[substreams::handlers::map]
+pub fn map_farm_emission_schedules(
+    events: FarmEmissionEvents,
+    params_store: StoreGetBigInt,
+) -> Result<FarmEmissionSchedules, substreams::errors::Error> {
+    let mut schedules = Vec::new();
+
+    for event in events.events {
+        if event.instruction_type != "fund" {
+            continue;
+        }
+        let prefix = format!("farm:{}", event.farm);
+        // Farm parameters as they were right before this funding
+        let previous = |name: &str| match params_store.get_at(event.ordinal - 1, format!("{}:{}", prefix, name)) {
+            Some(value) => parse_stored::<u64>(&value.to_string(), name),
+            None => Ok(0),
+        };
+
+        let reward_duration = previous("reward_duration")?;
+        let mut schedule = FarmEmissionSchedule {
+            farm: event.farm.clone(),
+            block_slot: event.block_slot,
+            block_time: event.block_time,
+            ordinal: event.ordinal,
+            reward_duration,
+            period_start: event.block_time,
+            period_end: event.block_time + reward_duration as i64,
+            amount_a: event.amount_a,
+            amount_b: event.amount_b,
+            reward_a_mint: event.reward_a_mint,
+            reward_b_mint: event.reward_b_mint,
+            ..Default::default()
+        };
+
+        // Farms created before the start block have an unknown reward duration
+        if reward_duration > 0 {
+            let duration = BigDecimal::from(reward_duration);
+            let remaining = (previous("last_fund_time")? as i64 + reward_duration as i64 - event.block_time).max(0);
+            let leftover = |previous_amount: u64| {
+                BigDecimal::from(previous_amount) * BigDecimal::from(remaining) / duration.clone()
+            };
+
+            let rate_a = (BigDecimal::from(event.amount_a) + leftover(previous("last_fund_a")?)) / duration.clone();
+            let rate_b = (BigDecimal::from(event.amount_b) + leftover(previous("last_fund_b")?)) / duration.clone();
+            schedule.reward_a_rate = rate_a.with_prec(20).to_string();
+            schedule.reward_b_rate = rate_b.with_prec(20).to_string();
+        }
+
+        substreams::log::info!(
+            "Farm {} funded in slot {}: {} / {} over {}s, rates {} / {} per second",
+            schedule.farm, schedule.block_slot, schedule.amount_a, schedule.amount_b, schedule.reward_duration,
+            schedule.reward_a_rate, schedule.reward_b_rate
+        );
+        schedules.push(schedule);
     }
+
+    Ok(FarmEmissionSchedules { schedules })
 }
 
-// Check if this is a significant event worth emitting
-fn is_significant_event(instruction_type: &str, instruction_data: &[u8]) -> bool {
-    // Always emit rebalancing events (vault optimization)
-    if instruction_type == "rebalance" {
-        return true;
+// Current emission schedule of each farm, keyed `farm:{farm}`
+#[substreams::handlers::store]
+pub fn store_farm_emission_schedules(schedules: FarmEmissionSchedules, store: StoreSetProto<FarmEmissionSchedule>) {
+    for schedule in schedules.schedules {
+        store.set(schedule.ordinal, format!("farm:{}", schedule.farm), &schedule);
     }
-    
-    // Always emit vault initialization
-    if instruction_type == "initialize" {
-        return true;
+}
+
+// Farm instructions that update the farm reward-per-token (`deposit`, `withdraw`, `claim` and `fund`), as
+// (ordinal, farm, block time, total staked after the instruction) in instruction order
+fn farm_reward_updates<'a>(positions: &'a FarmPositionEvents, emissions: &'a FarmEmissionEvents) -> Vec<(u64, &'a str, i64, u64)> {
+    let mut updates: Vec<(u64, &str, i64, u64)> = positions
+        .events
+        .iter()
+        .filter(|event| matches!(event.instruction_type.as_str(), "deposit" | "withdraw" | "claim"))
+        .map(|event| (event.ordinal, event.farm.as_str(), event.block_time, event.total_staked))
+        .chain(
+            emissions
+                .events
+                .iter()
+                .filter(|event| event.instruction_type == "fund")
+                .map(|event| (event.ordinal, event.farm.as_str(), event.block_time, event.total_staked)),
+        )
+        .collect();
+    updates.sort_by_key(|(ordinal, ..)| *ordinal);
+    updates
+}
+
+// Last reward update of each farm, keyed `farm:{farm}:{last_update_time|total_staked}`
+#[substreams::handlers::store]
+pub fn store_farm_reward_state(positions: FarmPositionEvents, emissions: FarmEmissionEvents, store: StoreSetBigInt) {
+    for (ordinal, farm, block_time, total_staked) in farm_reward_updates(&positions, &emissions) {
+        store.set(ordinal, format!("farm:{}:last_update_time", farm), &BigInt::from(block_time));
+        store.set(ordinal, format!("farm:{}:total_staked", farm), &BigInt::from(total_staked));
     }
-    
-    // For deposit/withdraw, check if amount is significant
-    if instruction_type == "deposit" || instruction_type == "withdraw" {
-        // Try to extract amount from instruction data (simplified)
-        if instruction_data.len() >= 9 {
-            // Assuming amount is stored as u64 in bytes 1-8
-            let amount_bytes = &instruction_data[1..9];
-            if amount_bytes.len() == 8 {
-                let amount = u64::from_le_bytes([
-                    amount_bytes[0], amount_bytes[1], amount_bytes[2], amount_bytes[3],
-                    amount_bytes[4], amount_bytes[5], amount_bytes[6], amount_bytes[7],
-                ]);
-                return amount >= SIGNIFICANT_AMOUNT_THRESHOLD;
+}
+
+// Reward-per-token of each farm, keyed `farm:{farm}:reward_{a|b}_per_token`. Every reward update accrues the
+// emission rate over the time since the previous update, capped at the end of the emission period, divided by the
+// total staked over that time. Farms start accruing at their first update after the start block.
+#[substreams::handlers::store]
+pub fn store_farm_reward_per_token(
+    positions: FarmPositionEvents,
+    emissions: FarmEmissionEvents,
+    state_store: StoreGetBigInt,
+    schedules_store: StoreGetProto<FarmEmissionSchedule>,
+    store: StoreAddBigDecimal,
+) {
+    for (ordinal, farm, block_time, _) in farm_reward_updates(&positions, &emissions) {
+        // Farm state and schedule as they were right before this update
+        let previous = |name: &str| state_store.get_at(ordinal - 1, format!("farm:{}:{}", farm, name));
+        let (Some(last_update_time), Some(total_staked)) = (previous("last_update_time"), previous("total_staked")) else {
+            continue;
+        };
+        let Some(schedule) = schedules_store.get_at(ordinal - 1, format!("farm:{}", farm)) else { continue };
+        if total_staked.is_zero() {
+            continue;
+        }
+
+        let last_update_time = (last_update_time.to_u64() as i64).max(schedule.period_start);
+        let elapsed = block_time.min(schedule.period_end) - last_update_time;
+        if elapsed <= 0 {
+            continue;
+        }
+
+        for (reward, rate) in [("a", &schedule.reward_a_rate), ("b", &schedule.reward_b_rate)] {
+            if rate.is_empty() {
+                continue;
             }
+            let Ok(rate) = BigDecimal::try_from(rate.as_str()) else {
+                substreams::log::info!("Skipping farm {} reward {}: invalid emission rate {}", farm, reward, rate);
+                continue;
+            };
+            let accrued = rate * BigDecimal::from(elapsed) / BigDecimal::from(total_staked.clone());
+            store.add(ordinal, format!("farm:{}:reward_{}_per_token", farm, reward), accrued.with_prec(30));
         }
     }
-    
-    // Always emit stake/unstake events (user behavior tracking)
-    if instruction_type == "stake" || instruction_type == "unstake" {
-        return true;
+}
+
+#[substreams::handlers::map]
+pub fn map_zap_events(instructions: MeteoraInstructions) -> Result<ZapEvents, substreams::errors::Error> {
+    zap_events(&instructions)
+}
+
+#[substreams::handlers::map]
+pub fn map_dlmm_position_events(instructions: MeteoraInstructions) -> Result<DlmmPositionEvents, substreams::errors::Error> {
+    dlmm_position_events(&instructions)
+}
+
+fn dlmm_position_key(position: &str) -> String {
+    format!("position:{}", position)
+}
+
+// Open DLMM positions with their owner and bin range, keyed `position:{position}`
+#[substreams::handlers::store]
+pub fn store_dlmm_positions(events: DlmmPositionEvents, store: StoreSetProto<DlmmPosition>) {
+    set_dlmm_positions(events, &store);
+}
+
+fn set_dlmm_positions<S: StoreSet<DlmmPosition>>(events: DlmmPositionEvents, store: &S) {
+    for event in events.events {
+        let key = dlmm_position_key(&event.position);
+        match event.instruction_type.as_str() {
+            "initialize_position" | "initialize_position_pda" | "initialize_position_by_operator" => store.set(
+                event.ordinal,
+                key,
+                &DlmmPosition {
+                    position: event.position,
+                    lb_pair: event.lb_pair,
+                    owner: event.owner,
+                    lower_bin_id: event.lower_bin_id,
+                    upper_bin_id: event.upper_bin_id,
+                    created_block_slot: event.block_slot,
+                    created_block_time: event.block_time,
+                },
+            ),
+            "close_position" => store.delete_prefix(event.ordinal as i64, &key),
+            _ => {}
+        }
+    }
+}
+
+// Lifetime totals of each open DLMM position, keyed
+// `position:{position}:{amount_x_added|amount_y_added|amount_x_removed|amount_y_removed|fee_x_claimed|fee_y_claimed|reward_{index}_claimed}`
+#[substreams::handlers::store]
+pub fn store_dlmm_position_totals(events: DlmmPositionEvents, store: StoreAddBigInt) {
+    add_dlmm_position_totals(events, &store);
+}
+
+fn add_dlmm_position_totals<S: StoreAdd<BigInt>>(events: DlmmPositionEvents, store: &S) {
+    for event in events.events {
+        let prefix = dlmm_position_key(&event.position);
+        if event.instruction_type == "close_position" {
+            store.delete_prefix(event.ordinal as i64, &format!("{}:", prefix));
+            continue;
+        }
+
+        let totals = [
+            ("amount_x_added", event.amount_x_added),
+            ("amount_y_added", event.amount_y_added),
+            ("amount_x_removed", event.amount_x_removed),
+            ("amount_y_removed", event.amount_y_removed),
+            ("fee_x_claimed", event.fee_x_claimed),
+            ("fee_y_claimed", event.fee_y_claimed),
+        ];
+        for (name, amount) in totals.into_iter().filter(|(_, amount)| *amount > 0) {
+            store.add(event.ordinal, format!("{}:{}", prefix, name), BigInt::from(amount));
+        }
+        if event.instruction_type == "claim_reward" {
+            store.add(
+                event.ordinal,
+                format!("{}:reward_{}_claimed", prefix, event.reward_index),
+                BigInt::from(event.reward_claimed),
+            );
+        }
     }
-    
-    // Always emit zap operations (complex DeFi operations)
-    if instruction_type == "zap_in" || instruction_type == "zap_out" {
-        return true;
+}
+
+#[substreams::handlers::map]
+pub fn map_dlmm_swap_events(instructions: MeteoraInstructions) -> Result<DlmmSwapEvents, substreams::errors::Error> {
+    dlmm_swap_events(&instructions)
+}
+
+fn dlmm_pair_key(lb_pair: &str) -> String {
+    format!("pair:{}", lb_pair)
+}
+
+// Bin step of every DLMM pair created since the start block, keyed `pair:{lb_pair}`
+#[substreams::handlers::store]
+pub fn store_dlmm_bin_steps(events: DlmmSwapEvents, store: StoreSetInt64) {
+    for pair in events.pairs {
+        store.set(pair.ordinal, dlmm_pair_key(&pair.lb_pair), &(pair.bin_step as i64));
     }
-    
-    false
 }
 
-// Extract amount from instruction data (simplified implementation)
-fn extract_amount_from_instruction(instruction_data: &[u8]) -> Option<u64> {
-    if instruction_data.len() >= 9 {
-        let amount_bytes = &instruction_data[1..9];
-        if amount_bytes.len() == 8 {
-            return Some(u64::from_le_bytes([
-                amount_bytes[0], amount_bytes[1], amount_bytes[2], amount_bytes[3],
-                amount_bytes[4], amount_bytes[5], amount_bytes[6], amount_bytes[7],
-            ]));
+// Active bin and price of a DLMM pair after each swap
+#[substreams::handlers::map]
+pub fn map_dlmm_price_ticks(
+    events: DlmmSwapEvents,
+    bin_steps: StoreGetInt64,
+) -> Result<DlmmPriceTicks, substreams::errors::Error> {
+    let ticks = events
+        .swaps
+        .into_iter()
+        .map(|swap| {
+            let mut tick = DlmmPriceTick {
+                lb_pair: swap.lb_pair.clone(),
+                block_slot: swap.block_slot,
+                block_time: swap.block_time,
+                ordinal: swap.ordinal,
+                tx_id: swap.tx_id,
+                active_bin_id: swap.end_bin_id,
+                token_x_mint: swap.token_x_mint,
+                token_y_mint: swap.token_y_mint,
+                ..Default::default()
+            };
+
+            if let Some(bin_step) = bin_steps.get_last(dlmm_pair_key(&swap.lb_pair)) {
+                tick.bin_step = bin_step as u32;
+                tick.price_per_lamport = dlmm::bin_price(swap.end_bin_id, bin_step as u16);
+                tick.price = tick.price_per_lamport
+                    * 10f64.powi(swap.token_x_decimals as i32 - swap.token_y_decimals as i32);
+            }
+            tick
+        })
+        .collect();
+
+    Ok(DlmmPriceTicks { ticks })
+}
+
+// Latest price tick of each DLMM pair, keyed `pair:{lb_pair}`
+#[substreams::handlers::store]
+pub fn store_dlmm_prices(ticks: DlmmPriceTicks, store: StoreSetProto<DlmmPriceTick>) {
+    for tick in ticks.ticks {
+        store.set(tick.ordinal, dlmm_pair_key(&tick.lb_pair), &tick);
+    }
+}
+
+// Quote mints whose price is taken as one USD: USDC and USDT
+const USD_STABLECOINS: [&str; 2] = [
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+];
+// Wrapped SOL, priced from its stablecoin pools and used as the hop for tokens quoted against SOL
+const SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const CANDLE_INTERVALS: [(&str, i64); 4] = [("1m", 60), ("5m", 300), ("1h", 3_600), ("1d", 86_400)];
+
+#[substreams::handlers::map]
+pub fn map_pool_swaps(instructions: MeteoraInstructions) -> Result<PoolSwaps, substreams::errors::Error> {
+    pool_swaps(&instructions)
+}
+
+fn token_price_key(mint: &str) -> String {
+    format!("mint:{}", mint)
+}
+
+// Latest USD price of SOL from its swaps against a USD stablecoin, keyed `mint:{SOL_MINT}`
+#[substreams::handlers::store]
+pub fn store_sol_usd_price(swaps: PoolSwaps, store: StoreSetBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        if price.is_zero() {
+            continue;
+        }
+
+        if swap.base_mint == SOL_MINT && USD_STABLECOINS.contains(&swap.quote_mint.as_str()) {
+            store.set(swap.ordinal, token_price_key(SOL_MINT), &price);
+        } else if swap.quote_mint == SOL_MINT && USD_STABLECOINS.contains(&swap.base_mint.as_str()) {
+            store.set(swap.ordinal, token_price_key(SOL_MINT), &(BigDecimal::one() / price).with_prec(20));
+        }
+    }
+}
+
+// Latest USD price of every token traded against a USD stablecoin, or against SOL through the SOL USD price,
+// keyed `mint:{mint}`
+#[substreams::handlers::store]
+pub fn store_token_usd_prices(swaps: PoolSwaps, sol_price_store: StoreGetBigDecimal, store: StoreSetBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        if price.is_zero() {
+            continue;
+        }
+
+        // USD price of the quote token, then of the base token, when it anchors the price of the other
+        let anchor_price = |mint: &str| {
+            if USD_STABLECOINS.contains(&mint) {
+                Some(BigDecimal::one())
+            } else if mint == SOL_MINT {
+                sol_price_store.get_at(swap.ordinal, token_price_key(SOL_MINT))
+            } else {
+                None
+            }
+        };
+        if let Some(quote_usd) = anchor_price(&swap.quote_mint) {
+            store.set(swap.ordinal, token_price_key(&swap.base_mint), &(price * quote_usd).with_prec(20));
+        } else if let Some(base_usd) = anchor_price(&swap.base_mint) {
+            store.set(swap.ordinal, token_price_key(&swap.quote_mint), &(base_usd / price).with_prec(20));
         }
     }
-    None
 }
 
-// Enhanced foundational store module with instruction parsing and smart filtering
+// Pool swaps with their USD volume, from the quote token USD price, or the base token one
 #[substreams::handlers::map]
-pub fn map_spl_instructions(
-    block: Block,
-    account_owner_store: FoundationalStore,
-) -> Result<Block, substreams::errors::Error> {
-    let mut meteora_transaction_count = 0;
-    let mut significant_events = 0;
-    let mut user_activity_map: HashMap<String, u32> = HashMap::new();
-    let slot = block.slot;
-
-    // Process transactions from the block
-    for trx in block.clone().transactions_owned() {
-        if let Some(ref transaction) = trx.transaction {
-            let accounts = trx.resolved_accounts();
-
-            for inst in &transaction.message.as_ref().unwrap_or(&Default::default()).instructions {
-                let program_id = bs58::encode(&accounts[inst.program_id_index as usize]).into_string();
-
-                if is_meteora_program(&program_id) {
-                    meteora_transaction_count += 1;
-                    
-                    // Parse instruction type and check if significant
-                    if let Some(instruction_type) = parse_meteora_instruction(&inst.data) {
-                        if is_significant_event(&instruction_type, &inst.data) {
-                            significant_events += 1;
-                            
-                            // Track user activity for analytics
-                            let signer = bs58::encode(&accounts[0]).into_string(); // Assuming first account is signer
-                            *user_activity_map.entry(signer.clone()).or_insert(0) += 1;
-                            
-                            // Use foundational store to get account owner data
-                            let account_key = accounts[inst.program_id_index as usize].clone();
-                            let response = account_owner_store.get(&account_key);
-                            
-                            if let Some(account_data) = response.value {
-                                substreams::log::info!(
-                                    "Significant Meteora event in slot {}: {} by user {} on program {} with account data length {}", 
-                                    slot, instruction_type, signer, program_id, account_data.value.len()
-                                );
-                            } else {
-                                substreams::log::info!(
-                                    "Significant Meteora event in slot {}: {} by user {} on program {} (no account owner data)", 
-                                    slot, instruction_type, signer, program_id
-                                );
-                            }
-                        } else {
-                            substreams::log::info!(
-                                "Meteora transaction in slot {}: {} on program {} (not significant)", 
-                                slot, instruction_type, program_id
-                            );
-                        }
-                    } else {
-                        substreams::log::info!("Found Meteora transaction in slot {}: program_id={} (unknown instruction)", slot, program_id);
-                    }
+pub fn map_pool_swaps_usd(
+    swaps: PoolSwaps,
+    usd_prices: StoreGetBigDecimal,
+) -> Result<PoolSwaps, substreams::errors::Error> {
+    let mut swaps = swaps.swaps;
+
+    for swap in swaps.iter_mut() {
+        let usd_price = |mint: &str| {
+            if USD_STABLECOINS.contains(&mint) {
+                return Some(BigDecimal::one());
+            }
+            usd_prices.get_at(swap.ordinal, token_price_key(mint))
+        };
+        let volume_usd = match (usd_price(&swap.quote_mint), usd_price(&swap.base_mint)) {
+            (Some(price), _) => BigDecimal::try_from(swap.quote_volume.as_str()).ok().map(|volume| volume * price),
+            (None, Some(price)) => BigDecimal::try_from(swap.base_volume.as_str()).ok().map(|volume| volume * price),
+            (None, None) => None,
+        };
+        swap.volume_usd = volume_usd.map(|volume| volume.with_prec(20).to_string()).unwrap_or_default();
+    }
+
+    Ok(PoolSwaps { swaps })
+}
+
+fn candle_key(pool: &str, interval: &str, bucket_start: i64) -> String {
+    format!("pool:{}:{}:{}", pool, interval, bucket_start)
+}
+
+// Candle buckets a priced swap falls into, as (interval, bucket start, key)
+fn candle_buckets(swap: &PoolSwap) -> Vec<(&'static str, i64, String)> {
+    if swap.price.is_empty() {
+        return Vec::new();
+    }
+
+    CANDLE_INTERVALS
+        .iter()
+        .map(|(interval, seconds)| {
+            let bucket_start = swap.block_time - swap.block_time.rem_euclid(*seconds);
+            (*interval, bucket_start, candle_key(&swap.pool, interval, bucket_start))
+        })
+        .collect()
+}
+
+fn candle_bucket_key(pool: &str, interval: &str) -> String {
+    format!("pool:{}:{}", pool, interval)
+}
+
+// Latest candle bucket of each pool and interval, keyed `pool:{pool}:{interval}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_buckets(swaps: PoolSwaps, store: StoreSetInt64) {
+    for swap in swaps.swaps {
+        for (interval, bucket_start, _) in candle_buckets(&swap) {
+            store.set(swap.ordinal, candle_bucket_key(&swap.pool, interval), &bucket_start);
+        }
+    }
+}
+
+// Delete the previous candle of the pool when the swap opens a new bucket, so the candle stores only keep the
+// current bucket of every pool and interval. Closed candles were already emitted by `map_pool_candles`.
+fn delete_closed_candles<S: StoreDelete>(swap: &PoolSwap, buckets: &StoreGetInt64, store: &S) {
+    for (interval, bucket_start, _) in candle_buckets(swap) {
+        let Some(previous) = buckets.get_at(swap.ordinal - 1, candle_bucket_key(&swap.pool, interval)) else {
+            continue;
+        };
+        if previous != bucket_start {
+            store.delete_prefix(swap.ordinal as i64, &candle_key(&swap.pool, interval, previous));
+        }
+    }
+}
+
+// Candle open prices, keyed `pool:{pool}:{interval}:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_open(swaps: PoolSwaps, buckets: StoreGetInt64, store: StoreSetIfNotExistsBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        delete_closed_candles(&swap, &buckets, &store);
+        for (_, _, key) in candle_buckets(&swap) {
+            store.set_if_not_exists(swap.ordinal, key, &price);
+        }
+    }
+}
+
+// Candle high prices, keyed `pool:{pool}:{interval}:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_high(swaps: PoolSwaps, buckets: StoreGetInt64, store: StoreMaxBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        delete_closed_candles(&swap, &buckets, &store);
+        for (_, _, key) in candle_buckets(&swap) {
+            store.max(swap.ordinal, key, price.clone());
+        }
+    }
+}
+
+// Candle low prices, keyed `pool:{pool}:{interval}:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_low(swaps: PoolSwaps, buckets: StoreGetInt64, store: StoreMinBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        delete_closed_candles(&swap, &buckets, &store);
+        for (_, _, key) in candle_buckets(&swap) {
+            store.min(swap.ordinal, key, price.clone());
+        }
+    }
+}
+
+// Candle close prices, keyed `pool:{pool}:{interval}:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_close(swaps: PoolSwaps, buckets: StoreGetInt64, store: StoreSetBigDecimal) {
+    for swap in swaps.swaps {
+        let Ok(price) = BigDecimal::try_from(swap.price.as_str()) else { continue };
+        delete_closed_candles(&swap, &buckets, &store);
+        for (_, _, key) in candle_buckets(&swap) {
+            store.set(swap.ordinal, key, &price);
+        }
+    }
+}
+
+// Candle volumes and trade counts, keyed `pool:{pool}:{interval}:{bucket_start}:{base_volume|quote_volume|volume_usd|trades}`
+#[substreams::handlers::store]
+pub fn store_pool_candle_volumes(swaps: PoolSwaps, buckets: StoreGetInt64, store: StoreAddBigDecimal) {
+    for swap in swaps.swaps {
+        let volumes = [
+            ("base_volume", swap.base_volume.as_str()),
+            ("quote_volume", swap.quote_volume.as_str()),
+            ("volume_usd", swap.volume_usd.as_str()),
+            ("trades", "1"),
+        ];
+        delete_closed_candles(&swap, &buckets, &store);
+        for (_, _, key) in candle_buckets(&swap) {
+            for (name, volume) in volumes.iter().filter(|(_, volume)| !volume.is_empty()) {
+                let Ok(volume) = BigDecimal::try_from(*volume) else { continue };
+                store.add(swap.ordinal, format!("{}:{}", key, name), volume);
+            }
+        }
+    }
+}
+
+// Candles updated by the swaps of the block, as they stand at the end of the block
+#[substreams::handlers::map]
+pub fn map_pool_candles(
+    swaps: PoolSwaps,
+    open_store: StoreGetBigDecimal,
+    high_store: StoreGetBigDecimal,
+    low_store: StoreGetBigDecimal,
+    close_store: StoreGetBigDecimal,
+    volume_store: StoreGetBigDecimal,
+) -> Result<PoolCandles, substreams::errors::Error> {
+    let mut candles: Vec<PoolCandle> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for swap in swaps.swaps.iter() {
+        for (interval, bucket_start, key) in candle_buckets(swap) {
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            let price = |store: &StoreGetBigDecimal| store.get_last(&key).map(|v| v.to_string()).unwrap_or_default();
+            let volume = |name: &str| {
+                volume_store
+                    .get_last(format!("{}:{}", key, name))
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+
+            candles.push(PoolCandle {
+                pool: swap.pool.clone(),
+                protocol: swap.protocol.clone(),
+                interval: interval.to_string(),
+                bucket_start,
+                base_mint: swap.base_mint.clone(),
+                quote_mint: swap.quote_mint.clone(),
+                open: price(&open_store),
+                high: price(&high_store),
+                low: price(&low_store),
+                close: price(&close_store),
+                base_volume: volume("base_volume"),
+                quote_volume: volume("quote_volume"),
+                volume_usd: volume("volume_usd"),
+                trades: match volume("trades").as_str() {
+                    "" => 0,
+                    trades => parse_stored(trades, "trade count")?,
+                },
+            });
+        }
+    }
+
+    Ok(PoolCandles { candles })
+}
+
+#[substreams::handlers::map]
+pub fn map_pools_created(instructions: MeteoraInstructions) -> Result<PoolsCreated, substreams::errors::Error> {
+    pools_created(&instructions)
+}
+
+// Registry of every pool created since the start block, keyed `pool:{pool}`
+#[substreams::handlers::store]
+pub fn store_pool_registry(pools: PoolsCreated, store: StoreSetIfNotExistsProto<PoolCreated>) {
+    for pool in pools.pools {
+        store.set_if_not_exists(pool.ordinal, format!("pool:{}", pool.pool), &pool);
+    }
+}
+
+#[substreams::handlers::map]
+pub fn map_decode_errors(instructions: MeteoraInstructions) -> Result<DecodeErrors, substreams::errors::Error> {
+    decode_errors(&instructions)
+}
+
+// Decoding failures since the start block, keyed `program:{program_id}:{reason}` and
+// `program:{program_id}:{kind}:{discriminator}:{reason}`
+#[substreams::handlers::store]
+pub fn store_decode_error_counts(errors: DecodeErrors, store: StoreAddInt64) {
+    add_decode_error_counts(errors, &store);
+}
+
+fn add_decode_error_counts<S: StoreAdd<i64>>(errors: DecodeErrors, store: &S) {
+    for error in errors.errors {
+        store.add(error.ordinal, format!("program:{}:{}", error.program_id, error.reason), 1);
+        store.add(
+            error.ordinal,
+            format!("program:{}:{}:{}:{}", error.program_id, error.kind, error.discriminator, error.reason),
+            1,
+        );
+    }
+}
+
+// Lifetime swap fees of each pool, keyed `pool:{pool}:mint:{fee_mint}:{lp_fee|protocol_fee|partner_fee|referral_fee}`
+#[substreams::handlers::store]
+pub fn store_pool_swap_fees(swaps: PoolSwaps, store: StoreAddBigInt) {
+    add_pool_swap_fees(swaps, &store);
+}
+
+fn add_pool_swap_fees<S: StoreAdd<BigInt>>(swaps: PoolSwaps, store: &S) {
+    for swap in swaps.swaps {
+        let fees = [
+            ("lp_fee", swap.lp_fee),
+            ("protocol_fee", swap.protocol_fee),
+            ("partner_fee", swap.partner_fee),
+            ("referral_fee", swap.referral_fee),
+        ];
+        for (name, fee) in fees.iter().filter(|(_, fee)| *fee > 0) {
+            let key = format!("pool:{}:mint:{}:{}", swap.pool, swap.fee_mint, name);
+            store.add(swap.ordinal, key, BigInt::from(*fee));
+        }
+    }
+}
+
+// Lifetime referral and host fees earned by each referrer, keyed `referrer:{referrer}:mint:{fee_mint}`
+#[substreams::handlers::store]
+pub fn store_referrer_fees(swaps: PoolSwaps, store: StoreAddBigInt) {
+    for swap in swaps.swaps.iter().filter(|swap| swap.referral_fee > 0 && !swap.referrer.is_empty()) {
+        let key = format!("referrer:{}:mint:{}", swap.referrer, swap.fee_mint);
+        store.add(swap.ordinal, key, BigInt::from(swap.referral_fee));
+    }
+}
+
+const FEE_RATE_INTERVAL: (&str, i64) = ("1h", 3_600);
+
+// Effective fee rate of every swap
+#[substreams::handlers::map]
+pub fn map_pool_fee_rates(swaps: PoolSwaps) -> Result<PoolFeeRates, substreams::errors::Error> {
+    Ok(meteora_decoder::pool_fee_rates(swaps))
+}
+
+// Latest effective fee rate of each pool, keyed `pool:{pool}`
+#[substreams::handlers::store]
+pub fn store_pool_fee_rates(fee_rates: PoolFeeRates, store: StoreSetProto<PoolFeeRate>) {
+    for fee_rate in fee_rates.fee_rates {
+        store.set(fee_rate.ordinal, format!("pool:{}", fee_rate.pool), &fee_rate);
+    }
+}
+
+// Hourly fee rate series of each pool, keyed `pool:{pool}:1h:{bucket_start}:{fee_rate_sum|swaps|bins_crossed}`
+#[substreams::handlers::store]
+pub fn store_pool_fee_rate_series(fee_rates: PoolFeeRates, store: StoreAddBigDecimal) {
+    let (interval, seconds) = FEE_RATE_INTERVAL;
+
+    for fee_rate in fee_rates.fee_rates {
+        let Ok(rate) = BigDecimal::try_from(fee_rate.fee_rate.as_str()) else { continue };
+        let bucket_start = fee_rate.block_time - fee_rate.block_time.rem_euclid(seconds);
+        let key = candle_key(&fee_rate.pool, interval, bucket_start);
+
+        store.add(fee_rate.ordinal, format!("{}:fee_rate_sum", key), rate);
+        store.add(fee_rate.ordinal, format!("{}:swaps", key), BigDecimal::one());
+        store.add(fee_rate.ordinal, format!("{}:bins_crossed", key), BigDecimal::from(fee_rate.bins_crossed as u64));
+    }
+}
+
+// Highest hourly fee rate of each pool, keyed `pool:{pool}:1h:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_pool_max_fee_rates(fee_rates: PoolFeeRates, store: StoreMaxBigDecimal) {
+    let (interval, seconds) = FEE_RATE_INTERVAL;
+
+    for fee_rate in fee_rates.fee_rates {
+        let Ok(rate) = BigDecimal::try_from(fee_rate.fee_rate.as_str()) else { continue };
+        let bucket_start = fee_rate.block_time - fee_rate.block_time.rem_euclid(seconds);
+        store.max(fee_rate.ordinal, candle_key(&fee_rate.pool, interval, bucket_start), rate);
+    }
+}
+
+#[substreams::handlers::map]
+pub fn map_dlmm_fee_parameter_events(instructions: MeteoraInstructions) -> Result<DlmmFeeParameterEvents, substreams::errors::Error> {
+    dlmm_fee_parameter_events(&instructions)
+}
+
+// Fee parameters of every DLMM preset created since the start block, keyed `preset:{preset}:{bin_step|base_factor|variable_fee_control}`
+#[substreams::handlers::store]
+pub fn store_dlmm_presets(events: DlmmFeeParameterEvents, store: StoreSetInt64) {
+    for event in events.events.iter().filter(|event| event.instruction_type == "initialize_preset_parameter") {
+        let prefix = format!("preset:{}", event.preset_parameter);
+        store.set(event.ordinal, format!("{}:bin_step", prefix), &(event.bin_step as i64));
+        store.set(event.ordinal, format!("{}:base_factor", prefix), &(event.base_factor as i64));
+        store.set(event.ordinal, format!("{}:variable_fee_control", prefix), &(event.variable_fee_control as i64));
+    }
+}
+
+// Fee parameters of each DLMM pair, keyed `pair:{lb_pair}:{bin_step|base_factor|variable_fee_control}`: those of its
+// preset at creation, then the base factor of every `update_fee_parameters`
+#[substreams::handlers::store]
+pub fn store_dlmm_fee_parameters(events: DlmmFeeParameterEvents, presets: StoreGetInt64, store: StoreSetInt64) {
+    for event in events.events {
+        let prefix = format!("pair:{}", event.lb_pair);
+        match event.instruction_type.as_str() {
+            "initialize_lb_pair" => {
+                store.set(event.ordinal, format!("{}:bin_step", prefix), &(event.bin_step as i64));
+                for name in ["base_factor", "variable_fee_control"] {
+                    let Some(value) = presets.get_last(format!("preset:{}:{}", event.preset_parameter, name)) else {
+                        substreams::log::info!("Pair {} created from unknown preset {}", event.lb_pair, event.preset_parameter);
+                        continue;
+                    };
+                    store.set(event.ordinal, format!("{}:{}", prefix, name), &value);
                 }
             }
+            "update_fee_parameters" => store.set(event.ordinal, format!("{}:base_factor", prefix), &(event.base_factor as i64)),
+            _ => {}
         }
     }
+}
 
-    if meteora_transaction_count > 0 {
-        substreams::log::info!(
-            "Processed {} Meteora transactions with foundational store in slot {}, {} significant events, {} active users", 
-            meteora_transaction_count, slot, significant_events, user_activity_map.len()
+// Fee rate of every DLMM swap, from the `fee_bps` of its `Swap` event, split into the base fee of the pair and the
+// variable fee on top, with the volatility accumulator the variable fee implies
+#[substreams::handlers::map]
+pub fn map_dlmm_fee_rates(
+    events: DlmmSwapEvents,
+    fee_parameters: StoreGetInt64,
+) -> Result<DlmmFeeRates, substreams::errors::Error> {
+    let mut fee_rates = Vec::new();
+
+    for swap in events.swaps {
+        let Ok(fee_bps) = swap.fee_bps.parse::<f64>() else {
+            substreams::log::info!("Skipping DLMM swap {} with invalid fee_bps {}", swap.tx_id, swap.fee_bps);
+            continue;
+        };
+        let fee_rate = fee_bps / 10_000.0;
+        let lb_pair = swap.lb_pair.clone();
+        let parameter = |name: &str| fee_parameters.get_at(swap.ordinal, format!("pair:{}:{}", lb_pair, name));
+
+        let mut rate = DlmmFeeRate {
+            block_slot: swap.block_slot,
+            block_time: swap.block_time,
+            tx_id: swap.tx_id,
+            ordinal: swap.ordinal,
+            lb_pair: swap.lb_pair,
+            fee_rate: fee_rate.to_string(),
+            active_bin_id: swap.end_bin_id,
+            ..Default::default()
+        };
+
+        if let (Some(bin_step), Some(base_factor), Some(variable_fee_control)) =
+            (parameter("bin_step"), parameter("base_factor"), parameter("variable_fee_control"))
+        {
+            let base_fee_rate = dlmm::base_fee_rate(base_factor as u16, bin_step as u16);
+            let variable_fee_rate = (fee_rate - base_fee_rate).max(0.0);
+            rate.bin_step = bin_step as u32;
+            rate.base_fee_rate = base_fee_rate.to_string();
+            rate.variable_fee_rate = variable_fee_rate.to_string();
+            rate.volatility_accumulator =
+                dlmm::volatility_accumulator(variable_fee_rate, variable_fee_control as u32, bin_step as u16);
+        }
+        fee_rates.push(rate);
+    }
+
+    Ok(DlmmFeeRates { fee_rates })
+}
+
+// Hourly base fee, variable fee and volatility accumulator series of each DLMM pair, keyed
+// `pool:{lb_pair}:1h:{bucket_start}:{base_fee_rate_sum|variable_fee_rate_sum|volatility_accumulator_sum|swaps}`,
+// over the swaps of pairs with known fee parameters
+#[substreams::handlers::store]
+pub fn store_dlmm_fee_rate_series(fee_rates: DlmmFeeRates, store: StoreAddBigDecimal) {
+    let (interval, seconds) = FEE_RATE_INTERVAL;
+
+    for fee_rate in fee_rates.fee_rates.iter().filter(|fee_rate| !fee_rate.base_fee_rate.is_empty()) {
+        let (Ok(base_fee_rate), Ok(variable_fee_rate)) = (
+            BigDecimal::try_from(fee_rate.base_fee_rate.as_str()),
+            BigDecimal::try_from(fee_rate.variable_fee_rate.as_str()),
+        ) else {
+            continue;
+        };
+        let bucket_start = fee_rate.block_time - fee_rate.block_time.rem_euclid(seconds);
+        let key = candle_key(&fee_rate.lb_pair, interval, bucket_start);
+
+        store.add(fee_rate.ordinal, format!("{}:base_fee_rate_sum", key), base_fee_rate);
+        store.add(fee_rate.ordinal, format!("{}:variable_fee_rate_sum", key), variable_fee_rate);
+        store.add(
+            fee_rate.ordinal,
+            format!("{}:volatility_accumulator_sum", key),
+            BigDecimal::from(fee_rate.volatility_accumulator as u64),
         );
-        
-        // Log power users (users with multiple transactions)
-        for (user, count) in &user_activity_map {
-            if *count > 1 {
-                substreams::log::info!("Power user {} has {} transactions in slot {}", user, count, slot);
+        store.add(fee_rate.ordinal, format!("{}:swaps", key), BigDecimal::one());
+    }
+}
+
+// Highest hourly volatility accumulator of each DLMM pair, keyed `pool:{lb_pair}:1h:{bucket_start}`
+#[substreams::handlers::store]
+pub fn store_dlmm_max_volatility(fee_rates: DlmmFeeRates, store: StoreMaxInt64) {
+    let (interval, seconds) = FEE_RATE_INTERVAL;
+
+    for fee_rate in fee_rates.fee_rates.iter().filter(|fee_rate| !fee_rate.base_fee_rate.is_empty()) {
+        let bucket_start = fee_rate.block_time - fee_rate.block_time.rem_euclid(seconds);
+        let key = candle_key(&fee_rate.lb_pair, interval, bucket_start);
+        store.max(fee_rate.ordinal, key, fee_rate.volatility_accumulator as i64);
+    }
+}
+
+// Row change for the SQL sinks, with its columns as (name, new value); empty values are left out so they load as NULL
+fn table_change(
+    table: &str,
+    pk: String,
+    ordinal: u64,
+    operation: table_change::Operation,
+    fields: Vec<(&str, String)>,
+) -> TableChange {
+    TableChange {
+        table: table.to_string(),
+        primary_key: Some(table_change::PrimaryKey::Pk(pk)),
+        ordinal,
+        operation: operation as i32,
+        fields: fields
+            .into_iter()
+            .filter(|(_, new_value)| !new_value.is_empty())
+            .map(|(name, new_value)| pb::sf::substreams::sink::database::v1::Field {
+                name: name.to_string(),
+                new_value,
+                old_value: String::new(),
+            })
+            .collect(),
+    }
+}
+
+// Table rows for substreams-sink-sql, matching `schema.sql` (Postgres) and `schema.clickhouse.sql`
+#[substreams::handlers::map]
+pub fn db_out(
+    instructions: Output,
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    farm_positions: FarmPositionEvents,
+    vault_flows: VaultFlows,
+    candles: PoolCandles,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
+    database_changes(instructions, pools, swaps, dlmm_positions, farm_positions, vault_flows, candles, dlmm_position_deltas)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn database_changes(
+    instructions: Output,
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    farm_positions: FarmPositionEvents,
+    vault_flows: VaultFlows,
+    candles: PoolCandles,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+) -> Result<DatabaseChanges, substreams::errors::Error> {
+    use table_change::Operation;
+    let mut table_changes = Vec::new();
+
+    for event in instructions.data {
+        let pk = instruction_id(&event);
+        table_changes.push(table_change(
+            "instructions",
+            pk,
+            0,
+            Operation::Create,
+            vec![
+                ("block_slot", event.block_slot.to_string()),
+                ("block_time", event.block_time.to_string()),
+                ("tx_id", event.tx_id),
+                ("instruction_index", event.instruction_index.to_string()),
+                ("inner_instruction_index", event.inner_instruction_index.to_string()),
+                ("is_inner_instruction", event.is_inner_instruction.to_string()),
+                ("program_id", event.program_id),
+                ("instruction_type", event.instruction_type),
+                ("signer", event.signer),
+                ("authority", event.authority),
+                ("fee_payer", event.fee_payer),
+                ("txn_fee", event.txn_fee.to_string()),
+            ],
+        ));
+    }
+
+    for pool in pools.pools {
+        table_changes.push(table_change(
+            "pools",
+            pool.pool.clone(),
+            pool.ordinal,
+            Operation::Create,
+            vec![
+                ("pool", pool.pool),
+                ("protocol", pool.protocol),
+                ("instruction", pool.instruction),
+                ("token_a_mint", pool.token_a_mint),
+                ("token_b_mint", pool.token_b_mint),
+                ("creator", pool.creator),
+                ("config", pool.config),
+                ("bin_step", pool.bin_step.to_string()),
+                ("fee_bps", pool.fee_bps),
+                ("lp_mint", pool.lp_mint),
+                ("permissioned", pool.permissioned.to_string()),
+                ("created_block_slot", pool.block_slot.to_string()),
+                ("created_block_time", pool.block_time.to_string()),
+                ("created_tx_id", pool.tx_id),
+            ],
+        ));
+    }
+
+    for swap in swaps.swaps {
+        table_changes.push(table_change(
+            "swaps",
+            format!("{}-{}", swap.block_slot, swap.ordinal),
+            swap.ordinal,
+            Operation::Create,
+            vec![
+                ("block_slot", swap.block_slot.to_string()),
+                ("block_time", swap.block_time.to_string()),
+                ("tx_id", swap.tx_id),
+                ("ordinal", swap.ordinal.to_string()),
+                ("protocol", swap.protocol),
+                ("pool", swap.pool),
+                ("user", swap.user),
+                ("base_mint", swap.base_mint),
+                ("quote_mint", swap.quote_mint),
+                ("base_amount", swap.base_amount.to_string()),
+                ("quote_amount", swap.quote_amount.to_string()),
+                ("is_buy", swap.is_buy.to_string()),
+                ("price", swap.price),
+                ("volume_usd", swap.volume_usd),
+                ("fee_mint", swap.fee_mint),
+                ("lp_fee", swap.lp_fee.to_string()),
+                ("protocol_fee", swap.protocol_fee.to_string()),
+                ("partner_fee", swap.partner_fee.to_string()),
+                ("referral_fee", swap.referral_fee.to_string()),
+                ("referrer", swap.referrer),
+            ],
+        ));
+    }
+
+    // store_dlmm_positions deletes a position when it is closed, with the position it held as the old value
+    let closed_positions: HashMap<String, DlmmPosition> = dlmm_position_deltas
+        .deltas
+        .into_iter()
+        .filter(|delta| delta.operation == store_delta::Operation::Delete)
+        .map(|delta| (delta.old_value.position.clone(), delta.old_value))
+        .collect();
+
+    for event in dlmm_positions.events {
+        match event.instruction_type.as_str() {
+            "initialize_position" | "initialize_position_pda" | "initialize_position_by_operator" => {
+                table_changes.push(table_change(
+                    "dlmm_positions",
+                    event.position.clone(),
+                    event.ordinal,
+                    Operation::Upsert,
+                    vec![
+                        ("position", event.position.clone()),
+                        ("lb_pair", event.lb_pair.clone()),
+                        ("owner", event.owner.clone()),
+                        ("lower_bin_id", event.lower_bin_id.to_string()),
+                        ("upper_bin_id", event.upper_bin_id.to_string()),
+                        ("created_block_slot", event.block_slot.to_string()),
+                        ("closed", "false".to_string()),
+                    ],
+                ))
+            }
+            "close_position" => {
+                // The full row is sent so ClickHouse, which replaces whole rows, keeps it; positions opened before the
+                // initial block of the store only update the columns the close event knows
+                let mut fields = vec![
+                    ("position", event.position.clone()),
+                    ("lb_pair", event.lb_pair.clone()),
+                    ("owner", event.owner.clone()),
+                ];
+                if let Some(position) = closed_positions.get(&event.position) {
+                    fields.push(("lower_bin_id", position.lower_bin_id.to_string()));
+                    fields.push(("upper_bin_id", position.upper_bin_id.to_string()));
+                    fields.push(("created_block_slot", position.created_block_slot.to_string()));
+                }
+                fields.push(("closed", "true".to_string()));
+                fields.push(("closed_block_slot", event.block_slot.to_string()));
+                table_changes.push(table_change("dlmm_positions", event.position.clone(), event.ordinal, Operation::Upsert, fields))
+            }
+            _ => {}
+        }
+
+        table_changes.push(table_change(
+            "dlmm_position_events",
+            format!("{}-{}", event.block_slot, event.ordinal),
+            event.ordinal,
+            Operation::Create,
+            vec![
+                ("block_slot", event.block_slot.to_string()),
+                ("block_time", event.block_time.to_string()),
+                ("tx_id", event.tx_id),
+                ("instruction_type", event.instruction_type),
+                ("lb_pair", event.lb_pair),
+                ("position", event.position),
+                ("owner", event.owner),
+                ("amount_x_added", event.amount_x_added.to_string()),
+                ("amount_y_added", event.amount_y_added.to_string()),
+                ("amount_x_removed", event.amount_x_removed.to_string()),
+                ("amount_y_removed", event.amount_y_removed.to_string()),
+                ("fee_x_claimed", event.fee_x_claimed.to_string()),
+                ("fee_y_claimed", event.fee_y_claimed.to_string()),
+                ("reward_claimed", event.reward_claimed.to_string()),
+            ],
+        ));
+    }
+
+    for event in farm_positions.events {
+        table_changes.push(table_change(
+            "farm_position_events",
+            format!("{}-{}", event.block_slot, event.ordinal),
+            event.ordinal,
+            Operation::Create,
+            vec![
+                ("block_slot", event.block_slot.to_string()),
+                ("block_time", event.block_time.to_string()),
+                ("tx_id", event.tx_id),
+                ("instruction_type", event.instruction_type),
+                ("farm", event.farm),
+                ("owner", event.owner),
+                ("staking_mint", event.staking_mint),
+                ("staked_amount", event.staked_amount),
+                ("reward_a_claimed", event.reward_a_claimed.to_string()),
+                ("reward_b_claimed", event.reward_b_claimed.to_string()),
+            ],
+        ));
+    }
+
+    for flow in vault_flows.flows {
+        table_changes.push(table_change(
+            "vault_flows",
+            format!("{}-{}", flow.vault, flow.block_slot),
+            0,
+            Operation::Create,
+            vec![
+                ("vault", flow.vault),
+                ("mint", flow.mint),
+                ("block_slot", flow.block_slot.to_string()),
+                ("block_time", flow.block_time.to_string()),
+                ("deposits", flow.deposits),
+                ("withdrawals", flow.withdrawals),
+                ("strategy_profit", flow.strategy_profit),
+                ("net_flow", flow.net_flow),
+            ],
+        ));
+    }
+
+    // Daily aggregates: the 1d candles, upserted as they fill up
+    for candle in candles.candles.into_iter().filter(|candle| candle.interval == "1d") {
+        table_changes.push(table_change(
+            "pool_daily",
+            format!("{}-{}", candle.pool, candle.bucket_start),
+            0,
+            Operation::Upsert,
+            vec![
+                ("pool", candle.pool),
+                ("day", candle.bucket_start.to_string()),
+                ("protocol", candle.protocol),
+                ("open", candle.open),
+                ("high", candle.high),
+                ("low", candle.low),
+                ("close", candle.close),
+                ("base_volume", candle.base_volume),
+                ("quote_volume", candle.quote_volume),
+                ("volume_usd", candle.volume_usd),
+                ("trades", candle.trades.to_string()),
+            ],
+        ));
+    }
+
+    Ok(DatabaseChanges { table_changes })
+}
+
+fn string_value(value: String) -> Value {
+    Value { typed: Some(value::Typed::String(value)) }
+}
+
+fn bigint_value(value: impl ToString) -> Value {
+    Value { typed: Some(value::Typed::Bigint(value.to_string())) }
+}
+
+fn bigdecimal_value(value: String) -> Value {
+    let value = if value.is_empty() { "0".to_string() } else { value };
+    Value { typed: Some(value::Typed::Bigdecimal(value)) }
+}
+
+fn int32_value(value: i32) -> Value {
+    Value { typed: Some(value::Typed::Int32(value)) }
+}
+
+fn bool_value(value: bool) -> Value {
+    Value { typed: Some(value::Typed::Bool(value)) }
+}
+
+// Entity upsert for graph-node, which merges these fields into the stored entity: fields left out keep their stored
+// value, so every write that may create the entity must set all its required fields
+fn entity_change(entity: &str, id: String, ordinal: u64, fields: Vec<(&str, Value)>) -> EntityChange {
+    EntityChange {
+        entity: entity.to_string(),
+        id,
+        ordinal,
+        operation: entity_change::Operation::Update as i32,
+        fields: fields
+            .into_iter()
+            .map(|(name, new_value)| pb::sf::substreams::entity::v1::Field {
+                name: name.to_string(),
+                new_value: Some(new_value),
+                old_value: None,
+            })
+            .collect(),
+    }
+}
+
+// Mint fields of a Farm entity, leaving out those the event could not resolve so known mints are not cleared
+fn farm_mint_fields(staking_mint: String, reward_a_mint: String, reward_b_mint: String) -> Vec<(&'static str, Value)> {
+    [("stakingMint", staking_mint), ("rewardAMint", reward_a_mint), ("rewardBMint", reward_b_mint)]
+        .into_iter()
+        .filter(|(_, mint)| !mint.is_empty())
+        .map(|(name, mint)| (name, string_value(mint)))
+        .collect()
+}
+
+fn user_entity(user: &str, block_slot: u64, block_time: i64, ordinal: u64) -> EntityChange {
+    entity_change(
+        "User",
+        user.to_string(),
+        ordinal,
+        vec![
+            ("lastActiveSlot", bigint_value(block_slot)),
+            ("lastActiveTimestamp", bigint_value(block_time)),
+        ],
+    )
+}
+
+// Entities of the Substreams-powered subgraph, matching `schema.graphql`
+#[substreams::handlers::map]
+pub fn graph_out(
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    farm_positions: FarmPositionEvents,
+    farm_emissions: FarmEmissionEvents,
+    candles: PoolCandles,
+    vault_tvl_deltas: Deltas<DeltaBigInt>,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+    farm_stake_deltas: Deltas<DeltaBigInt>,
+) -> Result<EntityChanges, substreams::errors::Error> {
+    graph_entity_changes(
+        pools,
+        swaps,
+        dlmm_positions,
+        farm_positions,
+        farm_emissions,
+        candles,
+        vault_tvl_deltas,
+        dlmm_position_deltas,
+        farm_stake_deltas,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn graph_entity_changes(
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    farm_positions: FarmPositionEvents,
+    farm_emissions: FarmEmissionEvents,
+    candles: PoolCandles,
+    vault_tvl_deltas: Deltas<DeltaBigInt>,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+    farm_stake_deltas: Deltas<DeltaBigInt>,
+) -> Result<EntityChanges, substreams::errors::Error> {
+    let mut entity_changes = Vec::new();
+
+    for pool in pools.pools {
+        entity_changes.push(entity_change(
+            "Pool",
+            pool.pool,
+            pool.ordinal,
+            vec![
+                ("protocol", string_value(pool.protocol)),
+                ("tokenAMint", string_value(pool.token_a_mint)),
+                ("tokenBMint", string_value(pool.token_b_mint)),
+                ("creator", string_value(pool.creator)),
+                ("config", string_value(pool.config)),
+                ("binStep", int32_value(pool.bin_step as i32)),
+                ("feeBps", bigdecimal_value(pool.fee_bps)),
+                ("createdAtSlot", bigint_value(pool.block_slot)),
+                ("createdAtTimestamp", bigint_value(pool.block_time)),
+                ("createdTx", string_value(pool.tx_id)),
+            ],
+        ));
+    }
+
+    for swap in swaps.swaps {
+        entity_changes.push(user_entity(&swap.user, swap.block_slot, swap.block_time, swap.ordinal));
+        entity_changes.push(entity_change(
+            "Swap",
+            format!("{}-{}", swap.block_slot, swap.ordinal),
+            swap.ordinal,
+            vec![
+                ("pool", string_value(swap.pool)),
+                ("protocol", string_value(swap.protocol)),
+                ("user", string_value(swap.user)),
+                ("baseMint", string_value(swap.base_mint)),
+                ("quoteMint", string_value(swap.quote_mint)),
+                ("baseAmount", bigint_value(swap.base_amount)),
+                ("quoteAmount", bigint_value(swap.quote_amount)),
+                ("isBuy", bool_value(swap.is_buy)),
+                ("price", bigdecimal_value(swap.price)),
+                ("volumeUsd", bigdecimal_value(swap.volume_usd)),
+                ("feeMint", string_value(swap.fee_mint)),
+                ("lpFee", bigint_value(swap.lp_fee)),
+                ("protocolFee", bigint_value(swap.protocol_fee)),
+                ("partnerFee", bigint_value(swap.partner_fee)),
+                ("referralFee", bigint_value(swap.referral_fee)),
+                ("slot", bigint_value(swap.block_slot)),
+                ("timestamp", bigint_value(swap.block_time)),
+                ("tx", string_value(swap.tx_id)),
+            ],
+        ));
+    }
+
+    // DLMM liquidity changes; the position entities come from the position store deltas below
+    for event in dlmm_positions.events {
+        let (entity, amount_x, amount_y) = match event.instruction_type.as_str() {
+            t if t.starts_with("add_liquidity") => ("Deposit", event.amount_x_added, event.amount_y_added),
+            t if t.starts_with("remove_liquidity") || t == "remove_all_liquidity" => {
+                ("Withdraw", event.amount_x_removed, event.amount_y_removed)
             }
+            _ => continue,
+        };
+        entity_changes.push(user_entity(&event.owner, event.block_slot, event.block_time, event.ordinal));
+        entity_changes.push(entity_change(
+            entity,
+            format!("{}-{}", event.block_slot, event.ordinal),
+            event.ordinal,
+            vec![
+                ("protocol", string_value("dlmm".to_string())),
+                ("pool", string_value(event.lb_pair)),
+                ("position", string_value(event.position)),
+                ("user", string_value(event.owner)),
+                ("amountA", bigint_value(amount_x)),
+                ("amountB", bigint_value(amount_y)),
+                ("slot", bigint_value(event.block_slot)),
+                ("timestamp", bigint_value(event.block_time)),
+                ("tx", string_value(event.tx_id)),
+            ],
+        ));
+    }
+
+    // Farm creations carry all three mints, fundings those of the vaults they move tokens through. Both read the
+    // staking vault balance; reward duration updates carry neither and may come before the farm entity exists
+    for event in farm_emissions.events {
+        if !matches!(event.instruction_type.as_str(), "initialize_pool" | "fund") {
+            continue;
         }
+        let mut fields = farm_mint_fields(event.staking_mint, event.reward_a_mint, event.reward_b_mint);
+        fields.push(("totalStaked", bigint_value(event.total_staked)));
+        fields.push(("lastActivitySlot", bigint_value(event.block_slot)));
+        entity_changes.push(entity_change("Farm", event.farm, event.ordinal, fields));
     }
 
-    Ok(block)
+    // Farm position deltas all come from this block's position events
+    let block_slot = farm_positions.events.first().map(|event| event.block_slot).unwrap_or_default();
+    for event in farm_positions.events {
+        let entity = match event.instruction_type.as_str() {
+            "deposit" => "Deposit",
+            "withdraw" => "Withdraw",
+            _ => continue,
+        };
+        let amount = event.staked_amount.trim_start_matches('-').to_string();
+        entity_changes.push(user_entity(&event.owner, event.block_slot, event.block_time, event.ordinal));
+        let mut fields = farm_mint_fields(event.staking_mint, event.reward_a_mint, event.reward_b_mint);
+        fields.push(("totalStaked", bigint_value(event.total_staked)));
+        fields.push(("lastActivitySlot", bigint_value(event.block_slot)));
+        entity_changes.push(entity_change("Farm", event.farm.clone(), event.ordinal, fields));
+        entity_changes.push(entity_change(
+            entity,
+            format!("{}-{}", event.block_slot, event.ordinal),
+            event.ordinal,
+            vec![
+                ("protocol", string_value("farm".to_string())),
+                ("pool", string_value(event.farm.clone())),
+                ("position", string_value(format!("{}-{}", event.farm, event.owner))),
+                ("user", string_value(event.owner)),
+                ("amountA", bigint_value(amount)),
+                ("amountB", bigint_value(0)),
+                ("slot", bigint_value(event.block_slot)),
+                ("timestamp", bigint_value(event.block_time)),
+                ("tx", string_value(event.tx_id)),
+            ],
+        ));
+    }
+
+    for candle in candles.candles.into_iter().filter(|candle| candle.interval == "1d") {
+        entity_changes.push(entity_change(
+            "DailySnapshot",
+            format!("{}-{}", candle.pool, candle.bucket_start),
+            0,
+            vec![
+                ("pool", string_value(candle.pool)),
+                ("protocol", string_value(candle.protocol)),
+                ("dayStartTimestamp", bigint_value(candle.bucket_start)),
+                ("open", bigdecimal_value(candle.open)),
+                ("high", bigdecimal_value(candle.high)),
+                ("low", bigdecimal_value(candle.low)),
+                ("close", bigdecimal_value(candle.close)),
+                ("baseVolume", bigdecimal_value(candle.base_volume)),
+                ("quoteVolume", bigdecimal_value(candle.quote_volume)),
+                ("volumeUsd", bigdecimal_value(candle.volume_usd)),
+                ("trades", bigint_value(candle.trades)),
+            ],
+        ));
+    }
+
+    // store_vault_tvl keys: `vault:{vault}:mint:{mint}`
+    for delta in vault_tvl_deltas.deltas {
+        let Some((vault, mint)) = delta.key.strip_prefix("vault:").and_then(|rest| rest.split_once(":mint:")) else {
+            continue;
+        };
+        entity_changes.push(entity_change(
+            "Vault",
+            vault.to_string(),
+            delta.ordinal,
+            vec![("mint", string_value(mint.to_string())), ("tvl", bigint_value(&delta.new_value))],
+        ));
+    }
+
+    // store_dlmm_positions deletes a position on close; the deleted value still carries the position
+    for delta in dlmm_position_deltas.deltas {
+        let (position, closed) = match delta.operation {
+            Operation::Create | Operation::Update => (delta.new_value, false),
+            Operation::Delete => (delta.old_value, true),
+            _ => continue,
+        };
+        entity_changes.push(entity_change(
+            "Position",
+            position.position,
+            delta.ordinal,
+            vec![
+                ("protocol", string_value("dlmm".to_string())),
+                ("pool", string_value(position.lb_pair)),
+                ("owner", string_value(position.owner)),
+                ("lowerBinId", int32_value(position.lower_bin_id)),
+                ("upperBinId", int32_value(position.upper_bin_id)),
+                ("staked", bigint_value(0)),
+                ("closed", bool_value(closed)),
+                ("createdAtSlot", bigint_value(position.created_block_slot)),
+            ],
+        ));
+    }
+
+    // store_farm_positions keys: `farm:{farm}:owner:{owner}:staked`, created by the first deposit and deleted when
+    // the stake account is closed
+    for delta in farm_stake_deltas.deltas {
+        let Some((farm, owner)) = delta
+            .key
+            .strip_prefix("farm:")
+            .and_then(|rest| rest.strip_suffix(":staked"))
+            .and_then(|rest| rest.split_once(":owner:"))
+        else {
+            continue;
+        };
+        let mut fields = vec![
+            ("protocol", string_value("farm".to_string())),
+            ("pool", string_value(farm.to_string())),
+            ("owner", string_value(owner.to_string())),
+            ("lowerBinId", int32_value(0)),
+            ("upperBinId", int32_value(0)),
+            ("staked", bigint_value(&delta.new_value)),
+            ("closed", bool_value(delta.operation == Operation::Delete)),
+        ];
+        // Updates keep the slot of the deposit that created the position
+        if delta.operation == Operation::Create {
+            fields.push(("createdAtSlot", bigint_value(block_slot)));
+        }
+        entity_changes.push(entity_change("Position", format!("{}-{}", farm, owner), delta.ordinal, fields));
+    }
+
+    Ok(EntityChanges { entity_changes })
 }
 
-// New vault analytics module - focuses on vault-specific metrics
+fn kv_set(key: String, value: Vec<u8>, ordinal: u64) -> KvOperation {
+    KvOperation { key, value, ordinal, r#type: kv_operation::Type::Set as i32 }
+}
+
+// Latest state for substreams-sink-kv. Values are protobuf-encoded messages, or decimal strings for amounts
 #[substreams::handlers::map]
-pub fn map_vault_analytics(block: Block) -> Result<Block, substreams::errors::Error> {
-    let mut vault_events = 0;
-    let mut total_deposits = 0u64;
-    let mut total_withdrawals = 0u64;
-    let mut rebalance_events = 0;
-    let slot = block.slot;
-
-    for trx in block.clone().transactions_owned() {
-        if let Some(ref transaction) = trx.transaction {
-            let accounts = trx.resolved_accounts();
-
-            for inst in &transaction.message.as_ref().unwrap_or(&Default::default()).instructions {
-                let program_id = bs58::encode(&accounts[inst.program_id_index as usize]).into_string();
-
-                // Focus only on Dynamic Vault Program
-                if program_id == DYNAMIC_VAULT_PROGRAM {
-                    if let Some(instruction_type) = parse_meteora_instruction(&inst.data) {
-                        match instruction_type.as_str() {
-                            "deposit" => {
-                                if let Some(amount) = extract_amount_from_instruction(&inst.data) {
-                                    total_deposits += amount;
-                                    vault_events += 1;
-                                    substreams::log::info!(
-                                        "Vault deposit in slot {}: {} lamports", 
-                                        slot, amount
-                                    );
-                                }
-                            },
-                            "withdraw" => {
-                                if let Some(amount) = extract_amount_from_instruction(&inst.data) {
-                                    total_withdrawals += amount;
-                                    vault_events += 1;
-                                    substreams::log::info!(
-                                        "Vault withdrawal in slot {}: {} lamports", 
-                                        slot, amount
-                                    );
-                                }
-                            },
-                            "rebalance" => {
-                                rebalance_events += 1;
-                                vault_events += 1;
-                                substreams::log::info!(
-                                    "Vault rebalancing event in slot {}", 
-                                    slot
-                                );
-                            },
-                            "initialize" => {
-                                vault_events += 1;
-                                substreams::log::info!(
-                                    "New vault initialized in slot {}", 
-                                    slot
-                                );
-                            },
-                            _ => {
-                                vault_events += 1;
-                                substreams::log::info!(
-                                    "Vault operation in slot {}: {}", 
-                                    slot, instruction_type
-                                );
-                            }
-                        }
-                    }
+pub fn kv_out(
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    vault_prices: VaultSharePrices,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+    farm_stake_deltas: Deltas<DeltaBigInt>,
+) -> Result<KvOperations, substreams::errors::Error> {
+    kv_operations(pools, swaps, vault_prices, dlmm_position_deltas, farm_stake_deltas)
+}
+
+fn kv_operations(
+    pools: PoolsCreated,
+    swaps: PoolSwaps,
+    vault_prices: VaultSharePrices,
+    dlmm_position_deltas: Deltas<DeltaProto<DlmmPosition>>,
+    farm_stake_deltas: Deltas<DeltaBigInt>,
+) -> Result<KvOperations, substreams::errors::Error> {
+    let mut operations = Vec::new();
+
+    for pool in pools.pools {
+        operations.push(kv_set(format!("pool:{}:info", pool.pool), pool.encode_to_vec(), pool.ordinal));
+    }
+
+    // Last swap of the pool, with its price and reserves
+    for swap in swaps.swaps {
+        operations.push(kv_set(format!("pool:{}:last_swap", swap.pool), swap.encode_to_vec(), swap.ordinal));
+    }
+
+    for (ordinal, price) in vault_prices.prices.iter().enumerate() {
+        operations.push(kv_set(format!("vault:{}:share_price", price.vault), price.encode_to_vec(), ordinal as u64));
+    }
+
+    for delta in dlmm_position_deltas.deltas {
+        match delta.operation {
+            Operation::Create | Operation::Update => {
+                let key = format!("user:{}:dlmm_position:{}", delta.new_value.owner, delta.new_value.position);
+                operations.push(kv_set(key, delta.new_value.encode_to_vec(), delta.ordinal));
+            }
+            Operation::Delete => operations.push(KvOperation {
+                key: format!("user:{}:dlmm_position:{}", delta.old_value.owner, delta.old_value.position),
+                value: Vec::new(),
+                ordinal: delta.ordinal,
+                r#type: kv_operation::Type::Delete as i32,
+            }),
+            _ => {}
+        }
+    }
+
+    // store_farm_positions keys: `farm:{farm}:owner:{owner}:staked`, created by the first deposit and deleted when
+    // the stake account is closed
+    for delta in farm_stake_deltas.deltas {
+        let Some((farm, owner)) = delta
+            .key
+            .strip_prefix("farm:")
+            .and_then(|rest| rest.strip_suffix(":staked"))
+            .and_then(|rest| rest.split_once(":owner:"))
+        else {
+            continue;
+        };
+        let key = format!("user:{}:farm_stake:{}", owner, farm);
+        operations.push(kv_set(key, delta.new_value.to_string().into_bytes(), delta.ordinal));
+    }
+
+    Ok(KvOperations { operations })
+}
+
+fn block_date(block_time: i64) -> String {
+    chrono::DateTime::from_timestamp(block_time, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+// Stored numeric strings are written by this package, so one that does not parse is a bug to surface, not a zero
+fn parse_stored<T>(value: &str, name: &str) -> Result<T, substreams::errors::Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value.parse().with_context(|| format!("invalid {} {:?}", name, value))
+}
+
+// Empty for amounts that were not priced, which the columns record as 0
+fn decimal_column(value: &str, name: &str) -> Result<f64, substreams::errors::Error> {
+    match value {
+        "" => Ok(0.0),
+        value => parse_stored(value, name),
+    }
+}
+
+// Flat, primitive-typed event tables for Parquet files, one repeated field per table
+#[substreams::handlers::map]
+pub fn parquet_out(
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    vault_flows: VaultFlows,
+    farm_positions: FarmPositionEvents,
+) -> Result<FlatEvents, substreams::errors::Error> {
+    flat_events(swaps, dlmm_positions, vault_flows, farm_positions)
+}
+
+fn flat_events(
+    swaps: PoolSwaps,
+    dlmm_positions: DlmmPositionEvents,
+    vault_flows: VaultFlows,
+    farm_positions: FarmPositionEvents,
+) -> Result<FlatEvents, substreams::errors::Error> {
+    let swaps = swaps
+        .swaps
+        .into_iter()
+        .map(|swap| {
+            Ok(SwapRow {
+                block_slot: swap.block_slot,
+                block_time: swap.block_time,
+                block_date: block_date(swap.block_time),
+                tx_id: swap.tx_id,
+                ordinal: swap.ordinal,
+                protocol: swap.protocol,
+                pool: swap.pool,
+                user: swap.user,
+                base_mint: swap.base_mint,
+                quote_mint: swap.quote_mint,
+                base_amount: swap.base_amount,
+                quote_amount: swap.quote_amount,
+                is_buy: swap.is_buy,
+                price: decimal_column(&swap.price, "price")?,
+                base_volume: decimal_column(&swap.base_volume, "base_volume")?,
+                quote_volume: decimal_column(&swap.quote_volume, "quote_volume")?,
+                volume_usd: decimal_column(&swap.volume_usd, "volume_usd")?,
+                fee_mint: swap.fee_mint,
+                lp_fee: swap.lp_fee,
+                protocol_fee: swap.protocol_fee,
+                partner_fee: swap.partner_fee,
+                referral_fee: swap.referral_fee,
+                referrer: swap.referrer,
+            })
+        })
+        .collect::<Result<_, substreams::errors::Error>>()?;
+
+    let liquidity_changes = dlmm_positions
+        .events
+        .into_iter()
+        .filter_map(|event| {
+            let (action, amount_a, amount_b) = match event.instruction_type.as_str() {
+                t if t.starts_with("add_liquidity") => ("add", event.amount_x_added, event.amount_y_added),
+                t if t.starts_with("remove_liquidity") || t == "remove_all_liquidity" => {
+                    ("remove", event.amount_x_removed, event.amount_y_removed)
                 }
+                _ => return None,
+            };
+            Some(LiquidityChangeRow {
+                block_slot: event.block_slot,
+                block_time: event.block_time,
+                block_date: block_date(event.block_time),
+                tx_id: event.tx_id,
+                ordinal: event.ordinal,
+                protocol: "dlmm".to_string(),
+                pool: event.lb_pair,
+                user: event.owner,
+                position: event.position,
+                action: action.to_string(),
+                instruction_type: event.instruction_type,
+                amount_a,
+                amount_b,
+                active_bin_id: event.active_bin_id,
+            })
+        })
+        .collect();
+
+    let vault_flows = vault_flows
+        .flows
+        .into_iter()
+        .map(|flow| {
+            VaultFlowRow {
+                block_slot: flow.block_slot,
+                block_time: flow.block_time,
+                block_date: block_date(flow.block_time),
+                vault: flow.vault,
+                mint: flow.mint,
+                deposits: flow.deposits,
+                withdrawals: flow.withdrawals,
+                strategy_profit: flow.strategy_profit,
+                net_flow: flow.net_flow,
+                deposit_count: flow.deposit_count,
+                withdrawal_count: flow.withdrawal_count,
+                rebalance_count: flow.rebalance_count,
             }
+        })
+        .collect();
+
+    let farm_actions = farm_positions
+        .events
+        .into_iter()
+        .map(|event| {
+            FarmActionRow {
+                block_slot: event.block_slot,
+                block_time: event.block_time,
+                block_date: block_date(event.block_time),
+                tx_id: event.tx_id,
+                ordinal: event.ordinal,
+                farm: event.farm,
+                user: event.owner,
+                action: event.instruction_type,
+                staking_mint: event.staking_mint,
+                staked_amount: event.staked_amount,
+                reward_a_claimed: event.reward_a_claimed,
+                reward_b_claimed: event.reward_b_claimed,
+                reward_a_mint: event.reward_a_mint,
+                reward_b_mint: event.reward_b_mint,
+            }
+        })
+        .collect();
+
+    Ok(FlatEvents { swaps, liquidity_changes, vault_flows, farm_actions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use substreams::pb::substreams::StoreDelta;
+
+    // Writes of a store handler, as `{operation} {ordinal} {key} {value}`
+    #[derive(Default)]
+    struct RecordingStore {
+        writes: RefCell<Vec<String>>,
+    }
+
+    impl StoreNew for RecordingStore {
+        fn new() -> Self {
+            Self::default()
         }
     }
 
-    if vault_events > 0 {
-        let net_flow = total_deposits.saturating_sub(total_withdrawals);
-        substreams::log::info!(
-            "Vault analytics for slot {}: {} events, {} deposits, {} withdrawals, net flow: {}, {} rebalances", 
-            slot, vault_events, total_deposits, total_withdrawals, net_flow, rebalance_events
+    impl StoreDelete for RecordingStore {
+        fn delete_prefix(&self, ord: i64, prefix: &String) {
+            self.writes.borrow_mut().push(format!("delete {} {}", ord, prefix));
+        }
+    }
+
+    impl<V: std::fmt::Display + Clone> StoreAdd<V> for RecordingStore {
+        fn add<K: AsRef<str>>(&self, ord: u64, key: K, value: V) {
+            self.writes.borrow_mut().push(format!("add {} {} {}", ord, key.as_ref(), value));
+        }
+
+        fn add_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: V) {
+            for key in keys {
+                self.add(ord, key, value.clone());
+            }
+        }
+    }
+
+    impl StoreSet<DlmmPosition> for RecordingStore {
+        fn set<K: AsRef<str>>(&self, ord: u64, key: K, value: &DlmmPosition) {
+            let range = format!("{}..={}", value.lower_bin_id, value.upper_bin_id);
+            self.writes.borrow_mut().push(format!("set {} {} {}", ord, key.as_ref(), range));
+        }
+
+        fn set_many<K: AsRef<str>>(&self, ord: u64, keys: &Vec<K>, value: &DlmmPosition) {
+            for key in keys {
+                self.set(ord, key, value);
+            }
+        }
+    }
+
+    fn writes(store: RecordingStore) -> Vec<String> {
+        store.writes.into_inner()
+    }
+
+    fn farm_event(ordinal: u64, instruction_type: &str, staked_amount: &str) -> pb::meteora::v1::FarmPositionEvent {
+        pb::meteora::v1::FarmPositionEvent {
+            block_slot: 300_000_400,
+            ordinal,
+            instruction_type: instruction_type.to_string(),
+            farm: "farm".to_string(),
+            owner: "owner".to_string(),
+            staked_amount: staked_amount.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn dlmm_event(ordinal: u64, instruction_type: &str) -> pb::meteora::v1::DlmmPositionEvent {
+        pb::meteora::v1::DlmmPositionEvent {
+            ordinal,
+            instruction_type: instruction_type.to_string(),
+            position: "position".to_string(),
+            lower_bin_id: -10,
+            upper_bin_id: 59,
+            ..Default::default()
+        }
+    }
+
+    fn bigint_delta(operation: Operation, ordinal: u64, key: &str, new_value: &str) -> DeltaBigInt {
+        DeltaBigInt::from(StoreDelta {
+            operation: operation as i32,
+            ordinal,
+            key: key.to_string(),
+            old_value: Vec::new(),
+            new_value: new_value.as_bytes().to_vec(),
+        })
+    }
+
+    fn entity_fields(change: &EntityChange) -> Vec<(&str, String)> {
+        change
+            .fields
+            .iter()
+            .map(|field| {
+                let value = match field.new_value.as_ref().and_then(|value| value.typed.as_ref()) {
+                    Some(value::Typed::String(value) | value::Typed::Bigint(value)) => value.clone(),
+                    Some(value::Typed::Bool(value)) => value.to_string(),
+                    Some(value::Typed::Int32(value)) => value.to_string(),
+                    other => format!("{:?}", other),
+                };
+                (field.name.as_str(), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn farm_positions_add_stakes_and_delete_closed_stakes() {
+        let store = RecordingStore::default();
+        let events = FarmPositionEvents {
+            events: vec![farm_event(1, "deposit", "100"), farm_event(2, "withdraw", "-100"), farm_event(3, "close_user", "")],
+        };
+        add_farm_positions(events, &store);
+        assert_eq!(
+            writes(store),
+            [
+                "add 1 farm:farm:owner:owner:staked 100",
+                "add 1 farm:farm:owner:owner:reward_a_claimed 0",
+                "add 1 farm:farm:owner:owner:reward_b_claimed 0",
+                "add 2 farm:farm:owner:owner:staked -100",
+                "add 2 farm:farm:owner:owner:reward_a_claimed 0",
+                "add 2 farm:farm:owner:owner:reward_b_claimed 0",
+                "delete 3 farm:farm:owner:owner:staked",
+            ]
+        );
+    }
+
+    #[test]
+    fn dlmm_positions_are_set_on_open_and_deleted_on_close() {
+        let store = RecordingStore::default();
+        let events = DlmmPositionEvents {
+            events: vec![dlmm_event(1, "initialize_position"), dlmm_event(2, "add_liquidity"), dlmm_event(3, "close_position")],
+        };
+        set_dlmm_positions(events, &store);
+        assert_eq!(writes(store), ["set 1 position:position -10..=59", "delete 3 position:position"]);
+    }
+
+    #[test]
+    fn dlmm_position_totals_add_moved_amounts_until_close() {
+        let store = RecordingStore::default();
+        let mut deposit = dlmm_event(1, "add_liquidity");
+        deposit.amount_x_added = 500;
+        let mut reward = dlmm_event(2, "claim_reward");
+        reward.reward_index = 1;
+        reward.reward_claimed = 7;
+        let events = DlmmPositionEvents { events: vec![deposit, reward, dlmm_event(3, "close_position")] };
+        add_dlmm_position_totals(events, &store);
+        assert_eq!(
+            writes(store),
+            [
+                "add 1 position:position:amount_x_added 500",
+                "add 2 position:position:reward_1_claimed 7",
+                "delete 3 position:position:",
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_errors_are_counted_per_reason_and_discriminator() {
+        let store = RecordingStore::default();
+        let error = pb::meteora::v1::DecodeError {
+            ordinal: 1,
+            program_id: "program".to_string(),
+            kind: "instruction".to_string(),
+            discriminator: "0011223344556677".to_string(),
+            reason: "unknown_discriminator".to_string(),
+            ..Default::default()
+        };
+        add_decode_error_counts(DecodeErrors { errors: vec![error] }, &store);
+        assert_eq!(
+            writes(store),
+            [
+                "add 1 program:program:unknown_discriminator 1",
+                "add 1 program:program:instruction:0011223344556677:unknown_discriminator 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn pool_swap_fees_add_charged_fees_only() {
+        let store = RecordingStore::default();
+        let swap = PoolSwap {
+            ordinal: 4,
+            pool: "pool".to_string(),
+            fee_mint: "mint".to_string(),
+            lp_fee: 30,
+            referral_fee: 5,
+            ..Default::default()
+        };
+        add_pool_swap_fees(PoolSwaps { swaps: vec![swap] }, &store);
+        assert_eq!(writes(store), ["add 4 pool:pool:mint:mint:lp_fee 30", "add 4 pool:pool:mint:mint:referral_fee 5"]);
+    }
+
+    #[test]
+    fn vault_tvl_skips_flows_without_mint_or_net_flow() {
+        let store = RecordingStore::default();
+        let flow = |mint: &str, net_flow: &str| pb::meteora::v1::VaultFlow {
+            vault: "vault".to_string(),
+            mint: mint.to_string(),
+            net_flow: net_flow.to_string(),
+            ..Default::default()
+        };
+        let flows = VaultFlows { flows: vec![flow("", "10"), flow("mint", "x"), flow("mint", "-25")] };
+        add_vault_tvl(flows, &store);
+        assert_eq!(writes(store), ["add 2 vault:vault:mint:mint -25"]);
+    }
+
+    #[test]
+    fn graph_farms_carry_required_fields_and_positions_close() {
+        let funding = pb::meteora::v1::FarmEmissionEvent {
+            block_slot: 300_000_400,
+            ordinal: 1,
+            instruction_type: "fund".to_string(),
+            farm: "farm".to_string(),
+            reward_a_mint: "reward".to_string(),
+            total_staked: 500,
+            ..Default::default()
+        };
+        let stake_deltas = Deltas {
+            deltas: vec![
+                bigint_delta(Operation::Create, 2, "farm:farm:owner:owner:staked", "100"),
+                bigint_delta(Operation::Update, 3, "farm:farm:owner:owner:staked", "40"),
+                bigint_delta(Operation::Delete, 4, "farm:farm:owner:owner:staked", ""),
+            ],
+        };
+        let changes = graph_entity_changes(
+            PoolsCreated::default(),
+            PoolSwaps::default(),
+            DlmmPositionEvents::default(),
+            FarmPositionEvents { events: vec![farm_event(2, "create_user", "0")] },
+            FarmEmissionEvents { events: vec![funding] },
+            PoolCandles::default(),
+            Deltas { deltas: Vec::new() },
+            Deltas { deltas: Vec::new() },
+            stake_deltas,
+        )
+        .unwrap()
+        .entity_changes;
+
+        assert_eq!(
+            entity_fields(&changes[0]),
+            [("rewardAMint", "reward".to_string()), ("totalStaked", "500".to_string()), ("lastActivitySlot", "300000400".to_string())]
         );
+        let positions: Vec<_> = changes[1..].iter().map(entity_fields).collect();
+        assert!(positions[0].contains(&("createdAtSlot", "300000400".to_string())));
+        assert!(positions[0].contains(&("closed", "false".to_string())));
+        assert!(positions[1].iter().all(|(name, _)| *name != "createdAtSlot"));
+        assert!(positions[2].contains(&("closed", "true".to_string())));
+    }
+
+    #[test]
+    fn kv_keys_pools_and_their_last_swap() {
+        let pool = PoolCreated { pool: "pool".to_string(), ordinal: 1, ..Default::default() };
+        let swap = PoolSwap { pool: "pool".to_string(), ordinal: 2, ..Default::default() };
+        let operations = kv_operations(
+            PoolsCreated { pools: vec![pool.clone()] },
+            PoolSwaps { swaps: vec![swap] },
+            VaultSharePrices::default(),
+            Deltas { deltas: Vec::new() },
+            Deltas { deltas: Vec::new() },
+        )
+        .unwrap()
+        .operations;
+        let keys: Vec<_> = operations.iter().map(|operation| operation.key.as_str()).collect();
+        assert_eq!(keys, ["pool:pool:info", "pool:pool:last_swap"]);
+        assert_eq!(PoolCreated::decode(operations[0].value.as_slice()).unwrap(), pool);
+    }
+
+    #[test]
+    fn database_rows_leave_empty_columns_out() {
+        let swap = PoolSwap {
+            block_slot: 300_000_500,
+            ordinal: 1,
+            pool: "pool".to_string(),
+            price: "149".to_string(),
+            ..Default::default()
+        };
+        let changes = database_changes(
+            Output::default(),
+            PoolsCreated::default(),
+            PoolSwaps { swaps: vec![swap] },
+            DlmmPositionEvents::default(),
+            FarmPositionEvents::default(),
+            VaultFlows::default(),
+            PoolCandles::default(),
+            Deltas { deltas: Vec::new() },
+        )
+        .unwrap()
+        .table_changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].table, "swaps");
+        assert_eq!(changes[0].primary_key, Some(table_change::PrimaryKey::Pk("300000500-1".to_string())));
+        assert!(changes[0].fields.iter().all(|field| !field.new_value.is_empty()));
+        assert!(!changes[0].fields.iter().any(|field| field.name == "volume_usd"));
     }
 
-    Ok(block)
+    #[test]
+    fn closed_dlmm_positions_are_upserted_as_full_rows() {
+        let position = DlmmPosition {
+            position: "position".to_string(),
+            lb_pair: "pair".to_string(),
+            owner: "owner".to_string(),
+            lower_bin_id: -10,
+            upper_bin_id: 59,
+            created_block_slot: 300_000_000,
+            ..Default::default()
+        };
+        // The close event has no bin range
+        let close = pb::meteora::v1::DlmmPositionEvent {
+            block_slot: 300_000_900,
+            lb_pair: "pair".to_string(),
+            owner: "owner".to_string(),
+            lower_bin_id: 0,
+            upper_bin_id: 0,
+            ..dlmm_event(1, "close_position")
+        };
+        let changes = database_changes(
+            Output::default(),
+            PoolsCreated::default(),
+            PoolSwaps::default(),
+            DlmmPositionEvents { events: vec![close] },
+            FarmPositionEvents::default(),
+            VaultFlows::default(),
+            PoolCandles::default(),
+            Deltas {
+                deltas: vec![DeltaProto {
+                    operation: Operation::Delete,
+                    ordinal: 1,
+                    key: dlmm_position_key("position"),
+                    old_value: position,
+                    new_value: DlmmPosition::default(),
+                }],
+            },
+        )
+        .unwrap()
+        .table_changes;
+
+        assert_eq!(changes[0].table, "dlmm_positions");
+        assert_eq!(changes[0].operation, table_change::Operation::Upsert as i32);
+        let fields: Vec<_> = changes[0].fields.iter().map(|field| (field.name.as_str(), field.new_value.as_str())).collect();
+        assert_eq!(
+            fields,
+            [
+                ("position", "position"),
+                ("lb_pair", "pair"),
+                ("owner", "owner"),
+                ("lower_bin_id", "-10"),
+                ("upper_bin_id", "59"),
+                ("created_block_slot", "300000000"),
+                ("closed", "true"),
+                ("closed_block_slot", "300000900"),
+            ]
+        );
+    }
+
+    #[test]
+    fn parquet_rows_record_unpriced_volumes_as_zero() {
+        let swap = PoolSwap { price: "149".to_string(), base_volume: "1".to_string(), ..Default::default() };
+        let events = flat_events(PoolSwaps { swaps: vec![swap] }, DlmmPositionEvents::default(), VaultFlows::default(), FarmPositionEvents::default())
+            .unwrap();
+        assert_eq!((events.swaps[0].price, events.swaps[0].base_volume, events.swaps[0].volume_usd), (149.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn parquet_rows_keep_sums_beyond_the_int64_range() {
+        let sum = "36893488147419103230".to_string();
+        let flow = pb::meteora::v1::VaultFlow { deposits: sum.clone(), net_flow: sum.clone(), ..Default::default() };
+        let events = flat_events(PoolSwaps::default(), DlmmPositionEvents::default(), VaultFlows { flows: vec![flow] }, FarmPositionEvents::default())
+            .unwrap();
+        assert_eq!((events.vault_flows[0].deposits.as_str(), events.vault_flows[0].net_flow.as_str()), (sum.as_str(), sum.as_str()));
+    }
 }
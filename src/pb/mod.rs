@@ -6,6 +6,7 @@ pub mod google {
         // @@protoc_insertion_point(google.protobuf)
     }
 }
+pub use meteora_decoder::pb::meteora;
 pub mod sf {
     pub mod solana {
         pub mod r#type {
@@ -20,6 +21,13 @@ pub mod sf {
     pub mod substreams {
         include!("sf.substreams.rs");
         // @@protoc_insertion_point(sf.substreams)
+        pub mod entity {
+            // @@protoc_insertion_point(attribute:sf.substreams.entity.v1)
+            pub mod v1 {
+                include!("sf.substreams.entity.v1.rs");
+                // @@protoc_insertion_point(sf.substreams.entity.v1)
+            }
+        }
         pub mod index {
             // @@protoc_insertion_point(attribute:sf.substreams.index.v1)
             pub mod v1 {
@@ -35,6 +43,20 @@ pub mod sf {
             }
         }
         pub mod sink {
+            pub mod database {
+                // @@protoc_insertion_point(attribute:sf.substreams.sink.database.v1)
+                pub mod v1 {
+                    include!("sf.substreams.sink.database.v1.rs");
+                    // @@protoc_insertion_point(sf.substreams.sink.database.v1)
+                }
+            }
+            pub mod kv {
+                // @@protoc_insertion_point(attribute:sf.substreams.sink.kv.v1)
+                pub mod v1 {
+                    include!("sf.substreams.sink.kv.v1.rs");
+                    // @@protoc_insertion_point(sf.substreams.sink.kv.v1)
+                }
+            }
             pub mod service {
                 // @@protoc_insertion_point(attribute:sf.substreams.sink.service.v1)
                 pub mod v1 {
@@ -65,6 +87,7 @@ pub mod sol {
             // @@protoc_insertion_point(sol.instructions.v1)
         }
     }
+    pub use meteora_decoder::pb::sol::spl;
     pub mod transactions {
         // @@protoc_insertion_point(attribute:sol.transactions.v1)
         pub mod v1 {